@@ -0,0 +1,330 @@
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use common::{Answer, Error, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Model = Device;
+
+    fn parse(&self, input: &str) -> Result<Device, Error> {
+        Ok(Device::new(parse_input(input)?))
+    }
+
+    fn part1(&self, device: &Device) -> Result<Answer, Error> {
+        Ok(get_frequency(&device.changes).into())
+    }
+
+    fn part2(&self, device: &Device) -> Result<Answer, Error> {
+        let repeat = device.repeat().ok_or_else(|| Error::unsolvable("no frequency is ever reached twice"))?;
+        Ok(repeat.frequency.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    fn export(&self, device: &Device, out: &mut dyn Write) -> Option<Result<(), Error>> {
+        Some(device.write_csv(out))
+    }
+}
+
+/// The frequency changes, and the first repeat once something has asked for it, so part 2 and
+/// the export only look for it once.
+pub struct Device {
+    changes: Vec<i32>,
+    repeat: OnceCell<Option<Repeat>>
+}
+
+impl Device {
+    pub fn new(changes: Vec<i32>) -> Device {
+        Device { changes, repeat: OnceCell::new() }
+    }
+
+    pub fn repeat(&self) -> Option<Repeat> {
+        *self.repeat.get_or_init(|| find_repeat(&self.changes))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<i32>, Error> {
+    input.lines().enumerate().try_fold(vec![], |mut acc, (i, line)| {
+        let change = line.parse().map_err(|_| {
+            Error::parse(line, "a frequency change like '+7' or '-3'").at_line(i + 1)
+        })?;
+        acc.push(change);
+        Ok(acc)
+    })
+}
+
+/// Generates `size` frequency changes. The last change is picked so the drift over a whole pass
+/// is smaller than the number of changes, which guarantees some frequency repeats: two of the
+/// frequencies in the first pass must then be equal modulo the drift.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as i64;
+    let mut changes: Vec<i64> = (1..size)
+        .map(|_| {
+            let change = rng.range(1..=20);
+            if rng.chance(1, 2) { change } else { -change }
+        })
+        .collect();
+    let drift = rng.range(-(size - 1)..=(size - 1));
+    changes.push(drift - changes.iter().sum::<i64>());
+    changes.iter().map(|change| format!("{:+}\n", change)).collect()
+}
+
+fn get_frequency(frequencies: &[i32]) -> i32 {
+    frequencies.iter().sum()
+}
+
+/// The first frequency that is reached twice, and the change that reached it the second time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub frequency: i64,
+    /// The pass over the list of changes, counting from 0
+    pub pass: usize,
+    /// The index of the change in the list
+    pub index: usize
+}
+
+/// Finds the first frequency that is reached twice, or proves that there is none, without
+/// cycling through the changes.
+///
+/// Before the `i`th change of pass `k` the frequency is `sums[i] + k * drift`, where `sums` are
+/// the frequencies before each change in the first pass and `drift` is the change over a whole
+/// pass. Unless the first pass repeats itself, two of these can only meet if their sums are
+/// equal modulo the drift, so the sums are grouped by their remainder. Within a group sorted in
+/// the direction of the drift, `sums[i]` reaches the next sum in the group after as many passes
+/// as the drift takes to cover the gap, and the earliest of those is the repeat.
+pub fn find_repeat(changes: &[i32]) -> Option<Repeat> {
+    let n = changes.len();
+    if n == 0 {
+        return None;
+    }
+
+    // A frequency reached twice in the first pass, or the start reached again at its end, which
+    // is the only way a pass without drift repeats
+    let mut sums = vec![0];
+    let mut seen: HashSet<i64> = sums.iter().copied().collect();
+    for (_, index, frequency) in running_sums(changes).take(n) {
+        if !seen.insert(frequency) {
+            return Some(Repeat { frequency, pass: 0, index });
+        }
+        sums.push(frequency);
+    }
+    let drift = sums.pop().unwrap_or_default();
+
+    let mut groups: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (i, &sum) in sums.iter().enumerate() {
+        groups.entry(sum.rem_euclid(drift)).or_default().push((sum, i));
+    }
+    let mut first: Option<(usize, i64)> = None;
+    for group in groups.values_mut() {
+        group.sort_unstable_by_key(|&(sum, _)| sum * drift.signum());
+        for pair in group.windows(2) {
+            let ((from, i), (to, _)) = (pair[0], pair[1]);
+            let passes = ((to - from) / drift) as usize;
+            // The number of changes made when `sums[i]` has moved on to `to`
+            let time = passes * n + i;
+            if first.is_none_or(|(earliest, _)| time < earliest) {
+                first = Some((time, to));
+            }
+        }
+    }
+    first.map(|(time, frequency)| Repeat { frequency, pass: (time - 1) / n, index: (time - 1) % n })
+}
+
+/// The frequency after every change, pass after pass, with the pass and index of the change.
+fn running_sums(changes: &[i32]) -> impl Iterator<Item = (usize, usize, i64)> + '_ {
+    changes.iter().cycle().enumerate().scan(0i64, move |frequency, (step, &change)| {
+        *frequency += i64::from(change);
+        Some((step / changes.len(), step % changes.len(), *frequency))
+    })
+}
+
+/// How the frequency moves while the device calibrates, until it reaches a frequency for the
+/// second time, or over the first pass if it never does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    /// The change over a whole pass
+    pub drift: i64,
+    pub min: i64,
+    pub max: i64,
+    /// The first repeat, whose `pass` is the number of whole passes made before it
+    pub repeat: Option<Repeat>
+}
+
+/// The frequency after a change, and the pass it was first reached in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub pass: usize,
+    pub index: usize,
+    pub change: i32,
+    pub frequency: i64,
+    pub first_pass: usize
+}
+
+impl Device {
+    /// Every change up to the first repeat, or over the first pass if nothing repeats.
+    pub fn steps(&self) -> impl Iterator<Item = Step> + '_ {
+        let repeat = self.repeat();
+        let count = match repeat {
+            Some(repeat) => repeat.pass * self.changes.len() + repeat.index + 1,
+            None => self.changes.len()
+        };
+        // Every frequency before the repeat is new, so only the repeated one was first reached
+        // in an earlier pass
+        let mut repeat_first_pass = repeat.filter(|repeat| repeat.frequency == 0).map(|_| 0);
+        running_sums(&self.changes).take(count).map(move |(pass, index, frequency)| {
+            let first_pass = match repeat {
+                Some(repeat) if repeat.frequency == frequency => *repeat_first_pass.get_or_insert(pass),
+                _ => pass
+            };
+            Step { pass, index, change: self.changes[index], frequency, first_pass }
+        })
+    }
+
+    pub fn calibrate(&self) -> Calibration {
+        let (min, max) = self.steps().fold((0, 0), |(min, max), step| (step.frequency.min(min), step.frequency.max(max)));
+        Calibration {
+            drift: self.changes.iter().map(|&change| i64::from(change)).sum(),
+            min,
+            max,
+            repeat: self.repeat()
+        }
+    }
+
+    /// Writes the steps as CSV, one row per change, after comment lines with the calibration.
+    pub fn write_csv(&self, out: &mut dyn Write) -> Result<(), Error> {
+        let calibration = self.calibrate();
+        writeln!(out, "# drift: {}", calibration.drift)?;
+        writeln!(out, "# min: {}", calibration.min)?;
+        writeln!(out, "# max: {}", calibration.max)?;
+        match calibration.repeat {
+            Some(repeat) => {
+                writeln!(out, "# repeat: {}", repeat.frequency)?;
+                writeln!(out, "# passes_before_repeat: {}", repeat.pass)?;
+            },
+            None => writeln!(out, "# repeat: none")?
+        }
+        writeln!(out, "pass,index,change,frequency,first_pass")?;
+        for step in self.steps() {
+            writeln!(out, "{},{},{},{},{}", step.pass, step.index, step.change, step.frequency, step.first_pass)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::Part;
+    use common::testing::{assert_generates, assert_matches_reference, assert_part1, assert_part2};
+
+    #[test]
+    fn part1_examples() {
+        assert_part1(&Day01, "+1\n-2\n+3\n+1", 3);
+        assert_part1(&Day01, "+1\n+1\n+1", 3);
+        assert_part1(&Day01, "+1\n+1\n-2", 0);
+        assert_part1(&Day01, "-1\n-2\n-3", -6);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_part2(&Day01, "+1\n-2\n+3\n+1", 2);
+        assert_part2(&Day01, "+1\n-1", 0);
+        assert_part2(&Day01, "+3\n+3\n+4\n-2\n-4", 10);
+        assert_part2(&Day01, "-6\n+3\n+8\n+5\n-6", 5);
+        assert_part2(&Day01, "+7\n+7\n-2\n-7\n-4", 14);
+    }
+
+    #[test]
+    fn repeats_report_where_they_happen() {
+        assert_eq!(find_repeat(&[3, 3, 4, -2, -4]), Some(Repeat { frequency: 10, pass: 1, index: 1 }));
+        assert_eq!(find_repeat(&[1, -2, 3, 1]), Some(Repeat { frequency: 2, pass: 1, index: 1 }));
+        assert_eq!(find_repeat(&[1, -1]), Some(Repeat { frequency: 0, pass: 0, index: 1 }));
+    }
+
+    #[test]
+    fn inputs_without_a_repeat() {
+        assert_eq!(find_repeat(&[1, 1]), None);
+        assert_eq!(find_repeat(&[5]), None);
+        assert_eq!(find_repeat(&[]), None);
+        assert!(Day01.part2(&Device::new(vec![5])).is_err());
+    }
+
+    #[test]
+    fn calibration_until_the_repeat() {
+        let device = Device::new(vec![3, 3, 4, -2, -4]);
+        let calibration = device.calibrate();
+        assert_eq!((calibration.drift, calibration.min, calibration.max), (4, 0, 10));
+        assert_eq!(calibration.repeat.map(|repeat| repeat.pass), Some(1));
+        let steps: Vec<Step> = device.steps().collect();
+        assert_eq!(steps.len(), 7);
+        assert_eq!(steps[6], Step { pass: 1, index: 1, change: 3, frequency: 10, first_pass: 0 });
+        let mut csv = vec![];
+        device.write_csv(&mut csv).unwrap();
+        let summary = "# drift: 4\n# min: 0\n# max: 10\n# repeat: 10\n# passes_before_repeat: 1\n";
+        assert!(csv.starts_with(format!("{}pass,index,change,frequency,first_pass\n0,0,3,3,0\n", summary).as_bytes()));
+    }
+
+    #[test]
+    fn calibration_without_a_repeat() {
+        let device = Device::new(vec![5, -3]);
+        assert_eq!((device.calibrate().min, device.calibrate().max, device.calibrate().repeat), (0, 5, None));
+        assert_eq!(device.steps().count(), 2);
+        let mut csv = vec![];
+        device.write_csv(&mut csv).unwrap();
+        assert!(csv.starts_with(b"# drift: 2\n# min: 0\n# max: 5\n# repeat: none\npass,"));
+    }
+
+    #[test]
+    fn calibration_of_millions_of_changes() {
+        let device = Device::new(vec![1_000_000, -999_999]);
+        assert_eq!(device.steps().count(), 2_000_000);
+        assert_eq!(device.calibrate().max, 1_999_999);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day01);
+    }
+
+    /// Cycles through the changes, for as many passes as the drift needs to carry the lowest
+    /// frequency of the first pass past the highest one, after which nothing new can repeat.
+    fn reference(part: Part, device: &Device) -> Option<Answer> {
+        let changes = &device.changes;
+        if part == Part::One || changes.is_empty() {
+            return None;
+        }
+        let drift: i64 = changes.iter().map(|&change| i64::from(change)).sum();
+        let (mut low, mut high, mut frequency) = (0i64, 0i64, 0i64);
+        for &change in changes {
+            frequency += i64::from(change);
+            low = low.min(frequency);
+            high = high.max(frequency);
+        }
+        let passes = if drift == 0 { 2 } else { (high - low) / drift.abs() + 2 };
+
+        let mut seen = HashSet::new();
+        let mut frequency = 0i64;
+        seen.insert(frequency);
+        for _ in 0..passes {
+            for &change in changes {
+                frequency += i64::from(change);
+                if !seen.insert(frequency) {
+                    return Some(frequency.into());
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day01, 50, &[1, 5, 20, 100], reference);
+    }
+}
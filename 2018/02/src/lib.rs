@@ -0,0 +1,342 @@
+use itertools::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::io::Write;
+use std::str::FromStr;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use common::{Answer, Error, Params, Parser, Rng, Solution};
+
+pub struct Day02 {
+    /// The number of times a letter must appear in an ID for each class of the checksum
+    multiplicities: Vec<usize>,
+    units: Units,
+    /// How many positions the pair of IDs in part 2 differs in, and the exported pairs at most
+    differences: usize
+}
+
+impl Default for Day02 {
+    fn default() -> Day02 {
+        Day02 {
+            multiplicities: vec![2, 3],
+            units: Units::Chars,
+            differences: 1
+        }
+    }
+}
+
+const MULTIPLICITIES_FORMAT: &str = "numbers separated by commas, like '2,3,4'";
+
+impl Solution for Day02 {
+    type Model = Vec<String>;
+
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        params.expect_only(&["multiplicities", "units", "differences"])?;
+        if let Some(multiplicities) = params.get::<String>("multiplicities", MULTIPLICITIES_FORMAT)? {
+            let mut parser = Parser::new(&multiplicities, MULTIPLICITIES_FORMAT);
+            self.multiplicities = parser.list(",", |parser| parser.int())?;
+            parser.end()?;
+            // No letter appears 0 times, so that class would always make the checksum 0
+            if self.multiplicities.contains(&0) {
+                return Err(Error::parse(&format!("multiplicities={}", multiplicities), "multiplicities of 1 or more"));
+            }
+            if self.multiplicities.iter().collect::<HashSet<_>>().len() < self.multiplicities.len() {
+                return Err(Error::parse(&format!("multiplicities={}", multiplicities), "each multiplicity only once"));
+            }
+        }
+        if let Some(units) = params.get("units", "bytes, chars or graphemes")? {
+            self.units = units;
+        }
+        if let Some(differences) = params.get("differences", "a number of differing positions")? {
+            self.differences = differences;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(&self, ids: &Vec<String>) -> Result<Answer, Error> {
+        Ok(checksum(ids, &self.multiplicities, self.units)?.into())
+    }
+
+    fn part2(&self, ids: &Vec<String>) -> Result<Answer, Error> {
+        let common = get_common_string(ids, self.differences).ok_or_else(|| {
+            let characters = if self.differences == 1 { "character" } else { "characters" };
+            Error::unsolvable(&format!("no box IDs differ by exactly {} {}", self.differences, characters))
+        })?;
+        Ok(common.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    fn export(&self, ids: &Vec<String>, out: &mut dyn Write) -> Option<Result<(), Error>> {
+        Some(write_pairs(ids, self.differences, out))
+    }
+}
+
+/// Writes every pair of IDs within `k` differences as CSV, one row per pair.
+fn write_pairs(ids: &[String], k: usize, out: &mut dyn Write) -> Result<(), Error> {
+    writeln!(out, "line_a,line_b,id_a,id_b,differences")?;
+    for (a, b, differences) in near_pairs(ids, k) {
+        writeln!(out, "{},{},{},{},{}", a + 1, b + 1, ids[a], ids[b], differences)?;
+    }
+    Ok(())
+}
+
+const ID_LENGTH: usize = 26;
+
+/// Generates `size` box IDs of random letters, where one pair of IDs differs in a single
+/// position.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let mut ids: Vec<Vec<char>> = (0..size.max(2) - 1)
+        .map(|_| (0..ID_LENGTH).map(|_| *rng.choose(&letters)).collect())
+        .collect();
+    let mut twin = rng.choose(&ids).clone();
+    let idx = rng.index(ID_LENGTH);
+    let original = twin[idx];
+    while twin[idx] == original {
+        twin[idx] = *rng.choose(&letters);
+    }
+    let at = rng.index(ids.len() + 1);
+    ids.insert(at, twin);
+    ids.iter().map(|id| id.iter().collect::<String>() + "\n").collect()
+}
+
+/// The letters two IDs that differ in exactly `k` positions have in common.
+fn get_common_string(ids: &[String], k: usize) -> Option<String> {
+    near_pairs(ids, k).into_iter().find(|&(_, _, differences)| differences == k).map(|(a, b, _)| {
+        ids[a].chars().zip(ids[b].chars()).filter(|(x, y)| x == y).map(|(x, _)| x).collect()
+    })
+}
+
+/// Finds every pair of IDs of the same length that differ in at most `k` positions, as indexes
+/// into `ids` in ascending order, with the number of positions they differ in.
+///
+/// Each ID is cut into `k + 1` segments, and put in a bucket for each of them, keyed by its
+/// length, which segment it is and the characters in it. `k` differences can only touch `k` of
+/// the segments, so any pair within `k` differences agrees on a whole segment and meets in its
+/// bucket. Only the pairs that meet are compared.
+pub fn near_pairs(ids: &[String], k: usize) -> Vec<(usize, usize, usize)> {
+    let ids: Vec<Vec<char>> = ids.iter().map(|id| id.chars().collect()).collect();
+    let mut buckets: HashMap<(usize, usize, &[char]), Vec<usize>> = HashMap::new();
+    for (i, id) in ids.iter().enumerate() {
+        // More segments than positions would only add empty ones
+        let segments = k.min(id.len()) + 1;
+        for segment in 0..segments {
+            let range = segment * id.len() / segments..(segment + 1) * id.len() / segments;
+            buckets.entry((id.len(), segment, &id[range])).or_default().push(i);
+        }
+    }
+    let candidates: HashSet<(usize, usize)> = buckets
+        .values()
+        .flat_map(|bucket| bucket.iter().array_combinations().map(|[&a, &b]| (a, b)))
+        .collect();
+
+    let mut pairs: Vec<(usize, usize, usize)> = candidates
+        .into_iter()
+        .map(|(a, b)| (a, b, ids[a].iter().zip(&ids[b]).filter(|(x, y)| x != y).count()))
+        .filter(|&(_, _, differences)| differences <= k)
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// What the letters of a box ID are, when counting how often each of them appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    Bytes,
+    Chars,
+    /// What a reader sees as a single letter, such as an `e` followed by a combining accent
+    Graphemes
+}
+
+impl FromStr for Units {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Units, Error> {
+        match s {
+            "bytes" => Ok(Units::Bytes),
+            "chars" => Ok(Units::Chars),
+            "graphemes" => Ok(Units::Graphemes),
+            _ => Err(Error::parse(s, "bytes, chars or graphemes"))
+        }
+    }
+}
+
+/// Counts, for each of the `multiplicities`, the IDs that have some letter exactly that many
+/// times. An ID with a letter twice and another letter three times counts for both 2 and 3.
+pub fn count_multiplicities(ids: &[String], multiplicities: &[usize], units: Units) -> BTreeMap<usize, usize> {
+    let mut counts: BTreeMap<usize, usize> = multiplicities.iter().map(|&m| (m, 0)).collect();
+    for id in ids {
+        let found = match units {
+            Units::Bytes => letter_counts(id.bytes()),
+            Units::Chars => letter_counts(id.chars()),
+            Units::Graphemes => letter_counts(id.graphemes(true))
+        };
+        for m in found {
+            if let Some(count) = counts.get_mut(&m) {
+                *count += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// How many times each letter appears, without duplicates.
+fn letter_counts<T: Hash + Eq, I: Iterator<Item = T>>(letters: I) -> HashSet<usize> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    for letter in letters {
+        *counts.entry(letter).or_default() += 1;
+    }
+    counts.into_values().collect()
+}
+
+/// Multiplies the counts of every class together, which for letters appearing exactly twice and
+/// exactly three times is the checksum of the puzzle.
+pub fn checksum(ids: &[String], multiplicities: &[usize], units: Units) -> Result<usize, Error> {
+    count_multiplicities(ids, multiplicities, units)
+        .values()
+        .try_fold(1usize, |product, &count| product.checked_mul(count))
+        .ok_or_else(|| Error::unsolvable("the checksum overflows a usize"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use common::testing::{assert_generates, assert_matches_reference, assert_part1, assert_part2};
+
+    #[test]
+    fn part1_example() {
+        assert_part1(&Day02::default(), "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab", 12);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_part2(&Day02::default(), "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz", "fgij");
+    }
+
+    #[test]
+    fn pairs_within_k_differences() {
+        let ids: Vec<String> = "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz".lines().map(str::to_string).collect();
+        assert_eq!(near_pairs(&ids, 0), vec![]);
+        assert_eq!(near_pairs(&ids, 1), vec![(1, 4, 1)]);
+        assert_eq!(near_pairs(&ids, 2), vec![(0, 5, 2), (1, 4, 1)]);
+        assert_eq!(near_pairs(&ids, 5).len(), 21);
+        assert_eq!(near_pairs(&ids, usize::MAX).len(), 21);
+    }
+
+    #[test]
+    fn part2_with_more_differences() {
+        let example = "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz";
+        assert_part2(&Day02 { differences: 2, ..Day02::default() }, example, "ace");
+        let day = Day02 { differences: 0, ..Day02::default() };
+        assert!(matches!(day.part2(&day.parse(example).unwrap()), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn pairs_only_of_the_same_length() {
+        let ids: Vec<String> = "abcd\nabc\nabce\nab".lines().map(str::to_string).collect();
+        assert_eq!(near_pairs(&ids, 1), vec![(0, 2, 1)]);
+        assert_eq!(near_pairs(&ids, 4), vec![(0, 2, 1)]);
+    }
+
+    #[test]
+    fn pairs_match_comparing_every_pair() {
+        for seed in 0..10 {
+            let ids: Vec<String> = generate_input(&mut Rng::new(seed), 30).lines().map(|id| id[..6].to_string()).collect();
+            for k in 0..=6 {
+                let mut expected = vec![];
+                for [a, b] in (0..ids.len()).array_combinations() {
+                    let differences = ids[a].chars().zip(ids[b].chars()).filter(|(x, y)| x != y).count();
+                    if differences <= k {
+                        expected.push((a, b, differences));
+                    }
+                }
+                assert_eq!(near_pairs(&ids, k), expected, "seed {}, k {}", seed, k);
+            }
+        }
+    }
+
+    #[test]
+    fn any_multiplicities() {
+        let ids: Vec<String> = "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\naaaabb".lines().map(str::to_string).collect();
+        let counts = count_multiplicities(&ids, &[2, 3, 4], Units::Chars);
+        assert_eq!(counts, BTreeMap::from([(2, 5), (3, 3), (4, 1)]));
+        assert_eq!(checksum(&ids, &[2, 3, 4], Units::Chars).unwrap(), 15);
+        assert_part1(&Day02 { multiplicities: vec![2, 3, 4], ..Day02::default() }, &ids.join("\n"), 15);
+    }
+
+    #[test]
+    fn multiplicities_are_listed_once() {
+        let mut day = Day02::default();
+        assert!(day.configure(&std::iter::once(("multiplicities", "2,2,3")).collect()).is_err());
+        assert!(day.configure(&std::iter::once(("multiplicities", "0,2")).collect()).is_err());
+        assert!(day.configure(&std::iter::once(("multiplicities", "3,2,4")).collect()).is_ok());
+        assert_eq!(day.multiplicities, vec![3, 2, 4]);
+    }
+
+    #[test]
+    fn bytes_chars_and_graphemes() {
+        // An 'e' with a combining accent, next to a plain 'e'
+        let accented = vec!["e\u{301}e".to_string()];
+        assert_eq!(count_multiplicities(&accented, &[2], Units::Chars)[&2], 1);
+        assert_eq!(count_multiplicities(&accented, &[2], Units::Graphemes)[&2], 0);
+        // 'é' and 'è' share their first byte in UTF-8
+        let precomposed = vec!["\u{e9}\u{e8}".to_string()];
+        assert_eq!(count_multiplicities(&precomposed, &[2], Units::Bytes)[&2], 1);
+        assert_eq!(count_multiplicities(&precomposed, &[2], Units::Chars)[&2], 0);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day02::default());
+    }
+
+    /// Counts the letters of each ID by sorting it and measuring the runs of equal letters, and
+    /// compares every pair of IDs position by position.
+    fn reference(part: Part, ids: &[String]) -> Option<Answer> {
+        match part {
+            Part::One => {
+                let (mut doubles, mut triples) = (0, 0);
+                for id in ids {
+                    let mut letters: Vec<char> = id.chars().collect();
+                    letters.sort_unstable();
+                    let mut runs = vec![];
+                    let mut start = 0;
+                    for i in 1..=letters.len() {
+                        if i == letters.len() || letters[i] != letters[start] {
+                            runs.push(i - start);
+                            start = i;
+                        }
+                    }
+                    doubles += runs.contains(&2) as usize;
+                    triples += runs.contains(&3) as usize;
+                }
+                Some((doubles * triples).into())
+            },
+            Part::Two => {
+                for (i, a) in ids.iter().enumerate() {
+                    for b in &ids[i + 1..] {
+                        let same: String = a.chars().zip(b.chars()).filter(|(x, y)| x == y).map(|(x, _)| x).collect();
+                        if same.len() + 1 == a.len() && a.len() == b.len() {
+                            return Some(same.into());
+                        }
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day02::default(), 20, &[2, 10, 50], |part, ids| reference(part, ids));
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+use common::image::heat;
+use common::{Answer, Error, Grid, Image, Parser, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Model = Vec<Claim>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Claim>, Error> {
+        parse_input(input)
+    }
+
+    fn part1(&self, claims: &Vec<Claim>) -> Result<Answer, Error> {
+        let area = i64::try_from(get_overlap_area(claims))
+            .map_err(|_| Error::unsolvable("the overlap area overflows a 64-bit answer"))?;
+        Ok(area.into())
+    }
+
+    fn part2(&self, claims: &Vec<Claim>) -> Result<Answer, Error> {
+        let claim = get_unique_claims(claims).into_iter().next().ok_or_else(|| Error::unsolvable("no unique claim found"))?;
+        Ok(claim.id.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    /// The fabric, one pixel per square inch, brighter the more claims there are on it.
+    fn image(&self, claims: &Vec<Claim>) -> Option<Result<Image, Error>> {
+        let fabric = match fabric(claims) {
+            Ok(fabric) => fabric,
+            Err(err) => return Some(Err(err))
+        };
+        let most = fabric.values().copied().max().unwrap_or(0).max(1);
+        Some(Ok(Image::from_grid(&fabric, 1, |_, &count| heat(f64::from(count) / f64::from(most)))))
+    }
+}
+
+/// The width and height of the fabric in the puzzle, in inches. Claims may reach past it.
+const FABRIC_SIZE: u32 = 1000;
+
+/// The widest and tallest fabric `Solution::image` draws, in inches
+const MAX_PICTURE_SIZE: u32 = 4000;
+
+#[derive(Debug)]
+pub struct Claim {
+    id: u32,
+    top: u32,
+    left: u32,
+    width: u32,
+    height: u32
+}
+
+impl Claim {
+    fn right(&self) -> u32 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> u32 {
+        self.top + self.height
+    }
+
+    fn has_area(&self) -> bool {
+        self.width > 0 && self.height > 0
+    }
+}
+
+/// The claims that share no square inch with any other claim, in the order they were made.
+fn get_unique_claims(claims: &[Claim]) -> Vec<&Claim> {
+    let overlapped = sweep(claims).overlapped;
+    claims.iter().zip(overlapped).filter(|(_, overlapped)| !overlapped).map(|(claim, _)| claim).collect()
+}
+
+fn get_overlap_area(claims: &[Claim]) -> u64 {
+    sweep(claims).overlap_area
+}
+
+/// What a sweep across the fabric finds.
+struct Sweep {
+    /// Square inches covered by two or more claims
+    overlap_area: u64,
+    /// Whether each claim shares a square inch with another claim
+    overlapped: Vec<bool>
+}
+
+/// Sweeps a line down the fabric, split into strips between the columns where claims start or
+/// end, adding each claim at its top edge and removing it at its bottom edge, in O(n log n).
+fn sweep(claims: &[Claim]) -> Sweep {
+    let mut overlapped = vec![false; claims.len()];
+    let sized: Vec<usize> = (0..claims.len()).filter(|&i| claims[i].has_area()).collect();
+    let mut xs: Vec<u32> = sized.iter().flat_map(|&i| [claims[i].left, claims[i].right()]).collect();
+    xs.sort_unstable();
+    xs.dedup();
+    if xs.len() < 2 {
+        return Sweep { overlap_area: 0, overlapped };
+    }
+    let strip = |x: u32| xs.binary_search(&x).unwrap_or_default();
+    let strips: Vec<(usize, usize)> = claims.iter().map(|claim| (strip(claim.left), strip(claim.right()))).collect();
+
+    // Claims end before others start at the same line
+    let mut events: Vec<(u32, bool, usize)> = sized
+        .iter()
+        .flat_map(|&i| [(claims[i].top, true, i), (claims[i].bottom(), false, i)])
+        .collect();
+    events.sort_unstable();
+
+    let mut tree = CoverTree::new(&xs);
+    let mut stabs = Stabs::new(xs.len() - 1);
+    let mut starts = BTreeSet::new();
+    let mut on_line = vec![false; claims.len()];
+    let mut overlap_area = 0;
+    let mut line = events[0].0;
+    for (y, adding, i) in events {
+        overlap_area += tree.covered_twice() * u64::from(y - line);
+        line = y;
+        let (from, to) = strips[i];
+        if !adding {
+            tree.add(from, to, -1);
+            starts.remove(&(from, i));
+            on_line[i] = false;
+            continue;
+        }
+
+        if tree.max(from, to) > 0 {
+            overlapped[i] = true;
+        }
+        let mut found = stabs.take(from);
+        found.extend(starts.range((from + 1, 0)..(to, 0)).map(|&(_, other)| other));
+        for other in found {
+            if on_line[other] && !overlapped[other] {
+                overlapped[other] = true;
+                starts.remove(&(strips[other].0, other));
+            }
+        }
+
+        tree.add(from, to, 1);
+        if !overlapped[i] {
+            stabs.insert(from, to, i);
+            starts.insert((from, i));
+        }
+        on_line[i] = true;
+    }
+    Sweep { overlap_area, overlapped }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct CoverNode {
+    /// Claims covering the whole range of the node, which are not passed on to its children
+    count: u32,
+    /// The most claims covering any strip in the range
+    max: u32,
+    width: u64,
+    once: u64,
+    twice: u64
+}
+
+/// A segment tree over the strips of the sweep line, counting the claims on each strip.
+struct CoverTree {
+    nodes: Vec<CoverNode>,
+    strips: usize
+}
+
+impl CoverTree {
+    fn new(xs: &[u32]) -> CoverTree {
+        let strips = xs.len() - 1;
+        let mut tree = CoverTree {
+            nodes: vec![CoverNode::default(); 4 * strips],
+            strips
+        };
+        tree.build(1, 0, strips, xs);
+        tree
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, xs: &[u32]) {
+        self.nodes[node].width = u64::from(xs[hi] - xs[lo]);
+        if hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            self.build(2 * node, lo, mid, xs);
+            self.build(2 * node + 1, mid, hi, xs);
+        }
+    }
+
+    /// The length of the line covered by two or more claims.
+    fn covered_twice(&self) -> u64 {
+        self.nodes[1].twice
+    }
+
+    /// Adds `delta` claims to the strips `from..to`.
+    fn add(&mut self, from: usize, to: usize, delta: i32) {
+        self.update(1, 0, self.strips, from, to, delta);
+    }
+
+    fn update(&mut self, node: usize, lo: usize, hi: usize, from: usize, to: usize, delta: i32) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.nodes[node].count = self.nodes[node].count.wrapping_add_signed(delta);
+        } else {
+            let mid = (lo + hi) / 2;
+            self.update(2 * node, lo, mid, from, to, delta);
+            self.update(2 * node + 1, mid, hi, from, to, delta);
+        }
+        self.pull(node, hi - lo == 1);
+    }
+
+    fn pull(&mut self, node: usize, leaf: bool) {
+        let (left, right) = if leaf {
+            (CoverNode::default(), CoverNode::default())
+        } else {
+            (self.nodes[2 * node], self.nodes[2 * node + 1])
+        };
+        let n = &mut self.nodes[node];
+        n.max = n.count + left.max.max(right.max);
+        n.once = if n.count >= 1 { n.width } else { left.once + right.once };
+        n.twice = match n.count {
+            0 => left.twice + right.twice,
+            1 => left.once + right.once,
+            _ => n.width
+        };
+    }
+
+    /// The most claims on any of the strips `from..to`.
+    fn max(&self, from: usize, to: usize) -> u32 {
+        self.query(1, 0, self.strips, from, to)
+    }
+
+    fn query(&self, node: usize, lo: usize, hi: usize, from: usize, to: usize) -> u32 {
+        if to <= lo || hi <= from {
+            return 0;
+        }
+        if from <= lo && hi <= to {
+            return self.nodes[node].max;
+        }
+        let mid = (lo + hi) / 2;
+        let below = self.query(2 * node, lo, mid, from, to).max(self.query(2 * node + 1, mid, hi, from, to));
+        self.nodes[node].count + below
+    }
+}
+
+/// The claims covering each strip, in a segment tree. Finding the claims on a strip takes them
+/// out of the tree.
+struct Stabs {
+    nodes: Vec<Vec<usize>>,
+    strips: usize
+}
+
+impl Stabs {
+    fn new(strips: usize) -> Stabs {
+        Stabs {
+            nodes: vec![vec![]; 4 * strips],
+            strips
+        }
+    }
+
+    fn insert(&mut self, from: usize, to: usize, claim: usize) {
+        self.insert_at(1, 0, self.strips, from, to, claim);
+    }
+
+    fn insert_at(&mut self, node: usize, lo: usize, hi: usize, from: usize, to: usize, claim: usize) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.nodes[node].push(claim);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.insert_at(2 * node, lo, mid, from, to, claim);
+        self.insert_at(2 * node + 1, mid, hi, from, to, claim);
+    }
+
+    /// Takes out the claims covering `strip`, and any claim that ended since it was inserted.
+    fn take(&mut self, strip: usize) -> Vec<usize> {
+        let mut found = vec![];
+        let (mut node, mut lo, mut hi) = (1, 0, self.strips);
+        loop {
+            found.append(&mut self.nodes[node]);
+            if hi - lo == 1 {
+                return found;
+            }
+            let mid = (lo + hi) / 2;
+            if strip < mid {
+                node *= 2;
+                hi = mid;
+            } else {
+                node = 2 * node + 1;
+                lo = mid;
+            }
+        }
+    }
+}
+
+/// The number of claims on each square inch of the fabric, which is at least as large as the
+/// fabric in the puzzle and grows to fit claims that reach past it.
+fn fabric(claims: &[Claim]) -> Result<Grid<u32>, Error> {
+    let width = claims.iter().map(Claim::right).max().unwrap_or(0).max(FABRIC_SIZE);
+    let height = claims.iter().map(Claim::bottom).max().unwrap_or(0).max(FABRIC_SIZE);
+    if width > MAX_PICTURE_SIZE || height > MAX_PICTURE_SIZE {
+        return Err(format!("The claims cover {}x{} inches, which is too large to draw", width, height).into());
+    }
+    Ok(claims.iter().fold(Grid::new(width as usize, height as usize, 0), |mut fabric, claim| {
+        for y in claim.top .. claim.bottom() {
+            for x in claim.left .. claim.right() {
+                fabric[(x as usize, y as usize)] += 1;
+            }
+        }
+        fabric
+    }))
+}
+
+/// Generates `size` claims on the fabric. Claims are packed into a corner of the fabric sized to
+/// the number of claims so they overlap now and then, and some are pushed out against the far
+/// edges of the fabric.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let area = (size as u32 * 10).clamp(40, FABRIC_SIZE);
+    (1..=size.max(1))
+        .map(|id| {
+            let width = rng.range(1..=30) as u32;
+            let height = rng.range(1..=30) as u32;
+            let left = if rng.chance(1, 10) {
+                FABRIC_SIZE - width
+            } else {
+                rng.range(0..=i64::from(area - width)) as u32
+            };
+            let top = if rng.chance(1, 10) {
+                FABRIC_SIZE - height
+            } else {
+                rng.range(0..=i64::from(area - height)) as u32
+            };
+            format!("#{} @ {},{}: {}x{}\n", id, left, top, width, height)
+        })
+        .collect()
+}
+
+const CLAIM_FORMAT: &str = "a claim like '#123 @ 3,2: 5x4'";
+
+fn parse_input(input: &str) -> Result<Vec<Claim>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_claim(line).map_err(|err| err.at_line(i + 1)))
+        .collect()
+}
+
+fn parse_claim(line: &str) -> Result<Claim, Error> {
+    let mut parser = Parser::new(line, CLAIM_FORMAT);
+    parser.token("#")?;
+    let id = parser.int()?;
+    parser.token(" @ ")?;
+    let [left, top] = parser.ints(",")?;
+    parser.token(": ")?;
+    let [width, height] = parser.ints("x")?;
+    parser.end()?;
+    let claim = Claim { id, top, left, width, height };
+    if claim.left.checked_add(claim.width).is_none() || claim.top.checked_add(claim.height).is_none() {
+        return Err(Error::parse(line, "a claim that fits within u32 coordinates"));
+    }
+    Ok(claim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use common::{ParseError, Part, Rng};
+    use common::testing::{assert_example, assert_generates, assert_matches_reference, assert_part2};
+
+    #[test]
+    fn example() {
+        assert_example(&Day03, "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2", 4, 3);
+    }
+
+    #[test]
+    fn claims_may_reach_past_the_fabric() {
+        assert!(Day03.parse("#1 @ 999,0: 2x1").is_ok());
+        assert!(Day03.parse("#1 @ 0,4294967295: 1x1").is_err());
+        assert_example(&Day03, "#1 @ 0,0: 100000x100000\n#2 @ 50000,50000: 100000x100000\n#3 @ 150000,0: 5x5", 2_500_000_000u64, 3);
+        let claims = Day03.parse("#1 @ 0,0: 5000x1").unwrap();
+        assert!(Day03.image(&claims).unwrap().is_err());
+    }
+
+    #[test]
+    fn overlap_areas_beyond_an_answer() {
+        let claims = Day03.parse("#1 @ 0,0: 4294967295x4294967295\n#2 @ 0,0: 4294967295x4294967295").unwrap();
+        assert!(matches!(Day03.part1(&claims), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn unique_claims_match_comparing_every_pair() {
+        let overlap = |a: &Claim, b: &Claim| a.left < b.right() && b.left < a.right() && a.top < b.bottom() && b.top < a.bottom();
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let claims: Vec<Claim> = (1..=rng.range(1..=30) as u32)
+                .map(|id| Claim {
+                    id,
+                    left: rng.range(0..=40) as u32,
+                    top: rng.range(0..=40) as u32,
+                    width: rng.range(0..=12) as u32,
+                    height: rng.range(0..=12) as u32
+                })
+                .collect();
+            let expected: Vec<u32> = claims
+                .iter()
+                .filter(|a| claims.iter().all(|b| a.id == b.id || !a.has_area() || !b.has_area() || !overlap(a, b)))
+                .map(|claim| claim.id)
+                .collect();
+            let ids: Vec<u32> = get_unique_claims(&claims).iter().map(|claim| claim.id).collect();
+            assert_eq!(ids, expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn every_unique_claim() {
+        let claims = Day03.parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 0,0: 0x9\n#5 @ 7,7: 1x1").unwrap();
+        let ids: Vec<u32> = get_unique_claims(&claims).iter().map(|claim| claim.id).collect();
+        assert_eq!(ids, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_column() {
+        let error = Day03.parse("#1 @ 1,3: 4x4\n#2 @ 3;1: 4x4");
+        assert!(matches!(error, Err(Error::Parse(ParseError { line: Some(2), column: Some(7), .. }))));
+    }
+
+    #[test]
+    fn image_of_the_fabric() {
+        let claims = Day03.parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2").unwrap();
+        let image = Day03.image(&claims).unwrap().unwrap();
+        assert_eq!((image.width(), image.height()), (1000, 1000));
+        assert_eq!(image.pixel((0, 0)), heat(0.0));
+        assert_eq!(image.pixel((1, 3)), heat(0.5));
+        assert_eq!(image.pixel((3, 3)), heat(1.0));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day03);
+    }
+
+    /// Counts the claims on every square inch one by one.
+    fn reference(part: Part, claims: &[Claim]) -> Option<Answer> {
+        let mut fabric: HashMap<(u32, u32), usize> = HashMap::new();
+        fn squares(claim: &Claim) -> Vec<(u32, u32)> {
+            let (left, top) = (claim.left, claim.top);
+            (left..left + claim.width).flat_map(|x| (top..top + claim.height).map(move |y| (x, y))).collect()
+        }
+        for claim in claims {
+            for square in squares(claim) {
+                *fabric.entry(square).or_default() += 1;
+            }
+        }
+        match part {
+            Part::One => Some(fabric.values().filter(|&&count| count >= 2).count().into()),
+            Part::Two => claims
+                .iter()
+                .find(|claim| squares(claim).iter().all(|square| fabric[square] == 1))
+                .map(|claim| claim.id.into())
+        }
+    }
+
+    #[test]
+    fn touching_claims_do_not_overlap() {
+        assert_example(&Day03, "#2 @ 2,4: 3x1\n#4 @ 5,2: 5x4", 0, 2);
+    }
+
+    #[test]
+    fn claims_overlapped_by_the_same_claim() {
+        assert_part2(&Day03, "#1 @ 3,0: 2x23\n#2 @ 2,4: 3x1\n#3 @ 2,7: 7x1\n#4 @ 5,2: 5x4", 4);
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day03, 30, &[2, 5, 10, 50], |part, claims| reference(part, claims));
+    }
+}
@@ -0,0 +1,396 @@
+use std::str::FromStr;
+use std::collections::{HashMap};
+
+use common::{parse_lines, Answer, Error, Params, Parser, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day04 {
+    /// Inspects this guard, instead of the guard each part's strategy picks
+    guard: Option<u32>
+}
+
+impl Solution for Day04 {
+    type Model = GuardLog;
+
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        params.expect_only(&["guard"])?;
+        self.guard = params.get("guard", "a guard id")?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<GuardLog, Error> {
+        let log_lines = parse_input(input)?;
+        Ok(GuardLog::build(log_lines))
+    }
+
+    fn part1(&self, guard_log: &GuardLog) -> Result<Answer, Error> {
+        let id = self.guard.unwrap_or_else(|| most_sleepy_guard(guard_log));
+        Ok(guard_solution(guard_log, id)?.into())
+    }
+
+    fn part2(&self, guard_log: &GuardLog) -> Result<Answer, Error> {
+        let id = self.guard.unwrap_or_else(|| most_predictable_guard(guard_log));
+        Ok(guard_solution(guard_log, id)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
+/// The guard id multiplied by the minute the guard is most often asleep, in 64 bits so large ids
+/// cannot overflow.
+fn guard_solution(guard_log: &GuardLog, id: u32) -> Result<u64, Error> {
+    let (time, _) = guard_log.get_prefered_sleep_minute(id)
+        .ok_or_else(|| Error::unsolvable(&format!("guard #{} is not in the log", id)))?;
+    Ok(u64::from(id) * u64::from(time))
+}
+
+fn most_predictable_guard(guard_log: &GuardLog) -> u32 {
+    struct FrequentGuard {
+        id: u32,
+        frequency: u32
+    }
+    let fg = guard_log.guard_ids().into_iter().fold(FrequentGuard {id: 0, frequency: 0}, |acc, id| {
+        let (_, frequency) = guard_log.get_prefered_sleep_minute(id).expect("Tried to get prefered sleep time for unknown guard");
+
+        if frequency > acc.frequency {
+            FrequentGuard {
+                id,
+                frequency
+            }
+        } else {
+            acc
+        }
+    });
+    fg.id
+}
+
+fn most_sleepy_guard(guard_log: &GuardLog) -> u32 {
+    let (id, _) = guard_log.guard_ids().into_iter().fold((0, 0), |mut acc, id| {
+        let time = guard_log.get_total_sleep_time(id).expect("Tried to get sleep time for unknown guard");
+        if time > acc.1 {
+            acc = (id, time);
+        }
+        acc
+    });
+    id
+}
+
+/// Generates the shuffled log of `size` shifts, shared by a pool of guards. Shifts start either
+/// just before or just after midnight, and one guard may never fall asleep at all.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let shifts = size.max(1);
+    let guards: Vec<u32> = (0..shifts / 3 + 1).map(|_| rng.range(1..=3500) as u32).collect();
+    let insomniac = if guards.len() > 1 && rng.chance(1, 2) { Some(guards[0]) } else { None };
+    let first_day = rng.range(1..=300) as u32;
+
+    let mut lines = vec![];
+    for shift in 0..shifts as u32 {
+        let day = first_day + shift;
+        let guard = *rng.choose(&guards);
+        let start = if rng.chance(1, 3) {
+            lines.push(format!("[{} 23:{:02}] Guard #{} begins shift", date(day - 1), rng.range(45..=59), guard));
+            0
+        } else {
+            let minute = rng.range(0..=10);
+            lines.push(format!("[{} 00:{:02}] Guard #{} begins shift", date(day), minute, guard));
+            minute
+        };
+
+        let naps = if insomniac == Some(guard) { 0 } else { rng.range(0..=3) as usize };
+        let mut minutes: Vec<i64> = (start + 1..60).collect();
+        rng.shuffle(&mut minutes);
+        let mut minutes = minutes[..naps * 2].to_vec();
+        minutes.sort_unstable();
+        for nap in minutes.chunks(2) {
+            lines.push(format!("[{} 00:{:02}] falls asleep", date(day), nap[0]));
+            lines.push(format!("[{} 00:{:02}] wakes up", date(day), nap[1]));
+        }
+    }
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// The date as 'YYYY-MM-DD', counting days from the first of January 1518.
+fn date(mut day: u32) -> String {
+    const MONTH_LENGTHS: [u32; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    let mut year = 1518;
+    let mut month = 0;
+    while day >= MONTH_LENGTHS[month] {
+        day -= MONTH_LENGTHS[month];
+        month += 1;
+        if month == 12 {
+            month = 0;
+            year += 1;
+        }
+    }
+    format!("{}-{:02}-{:02}", year, month + 1, day + 1)
+}
+
+fn parse_input(input: &str) -> Result<Vec<LogLine>, Error> {
+    let mut log_lines: Vec<LogLine> = parse_lines(input)?;
+    log_lines.sort_by_key(|a| a.time);
+    Ok(log_lines)
+}
+
+pub struct GuardLog {
+    log: HashMap<u32, Vec<LogLine>>,
+    minutes: HashMap<u32, [u32; 60]>
+}
+
+impl GuardLog {
+    fn build(log: Vec<LogLine>) -> Self {
+        let mut lines: HashMap<u32, Vec<LogLine>> = HashMap::new();
+        let mut minutes: HashMap<u32, [u32; 60]> = HashMap::new();
+
+        let mut guard_id = None;
+        for line in log.into_iter() {
+            guard_id = match line.get_guard_id() {
+                Some(id) => Some(id),
+                None => guard_id
+            };
+            if let Some(id) = guard_id {
+                (*lines.entry(id).or_default()).push(line.clone());
+            }
+
+        }
+
+        lines.iter().for_each(|(&id, lines)| {
+            let mut guard_minutes = [0; 60];
+            let mut sleep_time = 0;
+            for log in lines {
+                if log.is_fall_asleep() {
+                    sleep_time = log.minute;
+                }
+                if log.is_wakes_up() {
+                    for i in sleep_time..log.minute {
+                        guard_minutes[i as usize] += 1;
+                    }
+                }
+            }
+            minutes.insert(id, guard_minutes);
+        });
+
+        GuardLog {
+            log: lines,
+            minutes
+        }
+    }
+
+    /// The ids of all guards in the log, lowest first, so ties between guards always go the same
+    /// way.
+    fn guard_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self.log.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn get_prefered_sleep_minute(&self, id: u32) -> Option<(u32, u32)> {
+        let minutes = self.minutes.get(&id)?;
+        let (id, freq) = minutes.iter().enumerate().fold((0, 0), |acc, (id, &freq)| {
+            if acc.1 < freq {
+                (id, freq)
+            } else {
+                acc
+            }
+        });
+        Some((id as u32, freq))
+    }
+
+    fn get_total_sleep_time(&self, id: u32) -> Option<u64> {
+        let logs = self.log.get(&id)?;
+        let mut awake = true;
+        let mut total = 0;
+        let mut last_time = 0;
+        for log in logs {
+            if awake {
+                if log.is_fall_asleep() {
+                    awake = false;
+                }
+            } else if log.is_wakes_up() {
+                awake = true;
+                total += log.time - last_time;
+            }
+            last_time = log.time;
+        }
+        Some(total)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    time: u64,
+    minute: u32,
+    log: String
+}
+
+impl LogLine {
+    fn get_guard_id(&self) -> Option<u32> {
+        let mut parser = Parser::new(&self.log, "a guard like 'Guard #10 begins shift'");
+        parser.token("Guard #").ok()?;
+        let id = parser.int().ok()?;
+        parser.token(" begins shift").ok()?;
+        Some(id)
+    }
+
+    fn is_fall_asleep(&self) -> bool {
+        self.log == "falls asleep"
+    }
+
+    fn is_wakes_up(&self) -> bool {
+        self.log == "wakes up"
+    }
+}
+
+const LOG_LINE_FORMAT: &str = "a log line like '[1518-11-01 00:05] falls asleep'";
+
+impl FromStr for LogLine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<LogLine, Error> {
+        let mut parser = Parser::new(s, LOG_LINE_FORMAT);
+        parser.token("[")?;
+        // Small types keep the time below from overflowing
+        let y: u16 = parser.int()?;
+        parser.token("-")?;
+        let m: u8 = parser.int()?;
+        parser.token("-")?;
+        let d: u8 = parser.int()?;
+        parser.token(" ")?;
+        let hour: u8 = parser.int()?;
+        parser.token(":")?;
+        let min: u8 = parser.int()?;
+        parser.token("] ")?;
+        if hour >= 24 || min >= 60 {
+            return Err(Error::parse(s, LOG_LINE_FORMAT));
+        }
+        let (y, m, d, hour, min) = (u64::from(y), u64::from(m), u64::from(d), u64::from(hour), u64::from(min));
+
+        Ok(LogLine {
+            time: min + (hour * 60) + (d * 24 * 60) + (m * 24 * 60 * 31) + (y * 24 * 60 * 31 * 365),
+            minute: min as u32,
+            log: parser.rest().to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use common::Part;
+    use common::testing::{assert_example, assert_generates, assert_matches_reference, assert_part1};
+
+    const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+    #[test]
+    fn example() {
+        assert_example(&Day04::default(), EXAMPLE, 240, 4455);
+    }
+
+    #[test]
+    fn example_in_any_order() {
+        let shuffled = EXAMPLE.lines().rev().collect::<Vec<_>>().join("\n");
+        assert_example(&Day04::default(), &shuffled, 240, 4455);
+    }
+
+    #[test]
+    fn guard_log() {
+        let guard_log = Day04::default().parse(EXAMPLE).unwrap();
+        assert_eq!(guard_log.get_total_sleep_time(10), Some(50));
+        assert_eq!(guard_log.get_total_sleep_time(99), Some(30));
+        assert_eq!(guard_log.get_prefered_sleep_minute(10), Some((24, 2)));
+        assert_eq!(guard_log.get_prefered_sleep_minute(99), Some((45, 3)));
+    }
+
+    #[test]
+    fn chosen_guard() {
+        assert_part1(&Day04 { guard: Some(99) }, EXAMPLE, 99 * 45);
+    }
+
+    #[test]
+    fn large_guard_ids() {
+        let log = "\
+[1518-11-01 00:00] Guard #4000000000 begins shift
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:31] wakes up
+";
+        assert_example(&Day04::default(), log, 4_000_000_000u64 * 30, 4_000_000_000u64 * 30);
+    }
+
+    #[test]
+    fn times_outside_the_clock_are_errors() {
+        assert!("[1518-11-01 00:60] falls asleep".parse::<LogLine>().is_err());
+        assert!("[1518-11-01 24:00] falls asleep".parse::<LogLine>().is_err());
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day04::default());
+    }
+
+    /// Marks every minute each guard is asleep, and picks guards and minutes by scanning them
+    /// all, with ties going to the lowest guard id and the earliest minute. Without any guard
+    /// falling asleep neither strategy picks a guard, and logs where a guard wakes up without
+    /// falling asleep first are not covered.
+    fn reference(part: Part, guard_log: &GuardLog) -> Option<Answer> {
+        let mut minutes: BTreeMap<u32, [u32; 60]> = BTreeMap::new();
+        for (&id, lines) in &guard_log.log {
+            let guard_minutes = minutes.entry(id).or_insert([0; 60]);
+            let mut asleep_since = None;
+            for line in lines {
+                match (line.is_fall_asleep(), line.is_wakes_up(), asleep_since) {
+                    (true, _, None) => asleep_since = Some(line.minute),
+                    (_, true, Some(since)) if since < line.minute => {
+                        for minute in since..line.minute {
+                            guard_minutes[minute as usize] += 1;
+                        }
+                        asleep_since = None;
+                    },
+                    (false, false, None) => {},
+                    // Guards only wake up after falling asleep, later in the same hour
+                    _ => return None
+                }
+            }
+        }
+
+        let mut best = None;
+        for (&id, guard_minutes) in &minutes {
+            let most = *guard_minutes.iter().max().unwrap();
+            let score = match part {
+                Part::One => guard_minutes.iter().sum::<u32>(),
+                Part::Two => most
+            };
+            if score > 0 && best.is_none_or(|(_, best_score, _)| score > best_score) {
+                let minute = guard_minutes.iter().position(|&count| count == most).unwrap();
+                best = Some((id, score, minute as u32));
+            }
+        }
+        best.map(|(id, _, minute)| (id * minute).into())
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day04::default(), 50, &[2, 10, 50], reference);
+    }
+}
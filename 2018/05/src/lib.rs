@@ -0,0 +1,144 @@
+use common::{Answer, Error, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Model = Vec<u8>;
+
+    fn parse(&self, input: &str) -> Result<Vec<u8>, Error> {
+        Ok(input.trim().as_bytes().to_vec())
+    }
+
+    fn part1(&self, polymer: &Vec<u8>) -> Result<Answer, Error> {
+        Ok(chain_reaction(polymer.clone()).len().into())
+    }
+
+    fn part2(&self, polymer: &Vec<u8>) -> Result<Answer, Error> {
+        Ok(shortest_improved_polymer(polymer).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
+/// Generates a polymer of `size` units. The polymers use a random number of unit types, and now
+/// and then only a single one.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let types = if rng.chance(1, 8) { 1 } else { rng.range(1..=26) as u8 };
+    let mut polymer: String = (0..size.max(1))
+        .map(|_| {
+            let unit = b'a' + rng.below(u64::from(types)) as u8;
+            if rng.chance(1, 2) { unit.to_ascii_uppercase() as char } else { unit as char }
+        })
+        .collect();
+    polymer.push('\n');
+    polymer
+}
+
+fn shortest_improved_polymer(input: &[u8]) -> usize {
+    let truncated = chain_reaction(input.to_vec());
+
+    (b'a'..=b'z').fold(truncated.len(), |acc, letter| {
+        let polymer = input
+            .iter()
+            .cloned()
+            .filter(|&b| b != letter && b != (letter - 32))
+            .collect::<Vec<_>>();
+        let length = chain_reaction(polymer).len();
+        if acc < length {
+            acc
+        } else {
+            length
+        }
+    })
+}
+
+fn chain_reaction(input: Vec<u8>) -> Vec<u8> {
+    let mut input = input.to_vec();
+    loop {
+        let truncated = collapse(&input);
+        if truncated.len() == input.len() {
+            return truncated;
+        } else {
+            input = truncated;
+        }
+    }
+}
+
+fn collapse(polymer: &[u8]) -> Vec<u8> {
+    let (mut truncated, rem) = polymer.iter().fold((vec![], None), |trunc, c| {
+        let (mut truncated, previous) = trunc;
+        if previous.is_none() {
+            return (truncated, Some(*c));
+        }
+        let previous = previous.unwrap();
+        if (c - 32 == previous) || (c + 32 == previous) {
+            (truncated, None)
+        } else {
+            truncated.push(previous);
+            (truncated, Some(*c))
+        }
+    });
+    if let Some(rem) = rem {
+        truncated.push(rem);
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use common::testing::{assert_example, assert_generates, assert_matches_reference};
+
+    #[test]
+    fn example() {
+        assert_example(&Day05, "dabAcCaCBAcCcaDA", 10, 4);
+    }
+
+    #[test]
+    fn chain_reaction_examples() {
+        assert_eq!(chain_reaction(b"aA".to_vec()), b"");
+        assert_eq!(chain_reaction(b"abBA".to_vec()), b"");
+        assert_eq!(chain_reaction(b"abAB".to_vec()), b"abAB");
+        assert_eq!(chain_reaction(b"aabAAB".to_vec()), b"aabAAB");
+        assert_eq!(chain_reaction(b"dabAcCaCBAcCcaDA".to_vec()), b"dabCBAcaDA");
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day05);
+    }
+
+    /// Removes the first reacting pair of units, and starts over from the beginning until no
+    /// pair reacts.
+    fn react(polymer: &[u8]) -> usize {
+        let mut polymer = polymer.to_vec();
+        while let Some(i) = (1..polymer.len()).find(|&i| {
+            polymer[i - 1] != polymer[i] && polymer[i - 1].eq_ignore_ascii_case(&polymer[i])
+        }) {
+            polymer.drain(i - 1..=i);
+        }
+        polymer.len()
+    }
+
+    fn reference(part: Part, polymer: &[u8]) -> Option<Answer> {
+        match part {
+            Part::One => Some(react(polymer).into()),
+            Part::Two => (b'a'..=b'z')
+                .map(|unit| {
+                    let polymer: Vec<u8> = polymer.iter().copied().filter(|c| !c.eq_ignore_ascii_case(&unit)).collect();
+                    react(&polymer)
+                })
+                .min()
+                .map(Answer::from)
+        }
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day05, 50, &[1, 10, 100], |part, polymer| reference(part, polymer));
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeSet;
+
+use common::image::palette;
+use common::{parse_lines, Answer, Error, Grid, Image, Point2, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Model = Vec<Point>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Point>, Error> {
+        parse_input(input.trim())
+    }
+
+    fn part1(&self, points: &Vec<Point>) -> Result<Answer, Error> {
+        let plane = create_plane(points);
+        Ok(plane.get_largest(points.len() as u32).into())
+    }
+
+    fn part2(&self, points: &Vec<Point>) -> Result<Answer, Error> {
+        let plane = create_plane(points);
+        Ok(plane.get_distance_limited_region(10000, points).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    fn image(&self, points: &Vec<Point>) -> Option<Result<Image, Error>> {
+        Some(Ok(create_plane(points).image(points)))
+    }
+}
+
+/// Generates `size` distinct coordinates, spread over an area that grows with the number of
+/// coordinates.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let extent = (size as i64 * 8).clamp(10, 400);
+    let count = size.max(1);
+    let mut points = BTreeSet::new();
+    let mut lines = vec![];
+    while lines.len() < count {
+        let (x, y) = (rng.range(0..=extent), rng.range(0..=extent));
+        if points.insert((x, y)) {
+            lines.push(format!("{}, {}\n", x, y));
+        }
+    }
+    lines.concat()
+}
+
+fn create_plane(points: &[Point]) -> Plane {
+    let width = points.iter().fold(0, |max, p| max.max(p.x()).max(p.y()));
+
+    let mut plane = Plane::new(width + 2);
+    plane.fill(points);
+    plane
+}
+
+fn parse_input(input: &str) -> Result<Vec<Point>, Error> {
+    parse_lines(input)
+}
+
+#[derive(Debug, Clone)]
+struct Plane {
+    width: u32,
+    inner: Grid<Option<u32>>
+}
+
+impl Plane {
+    fn new(width: u32) -> Self {
+        Plane {
+            width,
+            inner: Grid::new(width as usize, width as usize, None)
+        }
+    }
+
+    fn get_largest(&self, max: u32) -> u32 {
+        let mut areas = vec![];
+        for i in 0..max {
+            if !self.is_infinite(i) {
+                areas.push(self.get_area(i));
+            }
+        }
+        areas.iter().fold(0, |acc, &n| {
+            if n >  acc {
+                n
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn fill(&mut self, points: &[Point]) {
+        let width = self.width;
+        self.inner = Grid::from_fn(width as usize, width as usize, |(x, y)| {
+            let coord = Point::new(x as u32, y as u32);
+            let val = points.iter().enumerate().fold((None, width * width), |cur, (index, point)| {
+                let distance = point.manhattan(&coord);
+                if distance < cur.1 {
+                    (Some(index as u32), distance)
+                } else if distance == cur.1 {
+                    (None, distance)
+                } else {
+                    cur
+                }
+            });
+            val.0
+        });
+    }
+
+    fn get_area(&self, index: u32) -> u32 {
+        self.inner.values().filter(|n| *n == &Some(index)).count() as u32
+    }
+
+    fn get_distance_limited_region(&self, limit: u32, points: &[Point]) -> u32 {
+        self.inner.positions().filter(|&(x, y)| {
+            let coord = Point::new(x as u32, y as u32);
+            let distance: u32 = points.iter().map(|p| {
+                p.manhattan(&coord)
+            }).sum();
+            distance < limit
+        }).count() as u32
+    }
+
+    /// Each area in its own colour, dimmed for the areas that go on forever. Squares as close to
+    /// two coordinates are dark grey, and the coordinates themselves white.
+    fn image(&self, points: &[Point]) -> Image {
+        let infinite: Vec<bool> = (0..points.len() as u32).map(|i| self.is_infinite(i)).collect();
+        Image::from_grid(&self.inner, 2, |(x, y), &closest| {
+            match closest {
+                _ if points.contains(&Point::new(x as u32, y as u32)) => [255, 255, 255],
+                Some(i) if infinite[i as usize] => palette(i as usize).map(|c| c / 3),
+                Some(i) => palette(i as usize),
+                None => [40, 40, 40]
+            }
+        })
+    }
+
+    fn is_infinite(&self, index: u32) -> bool {
+        let last = self.width as usize - 1;
+        let index = Some(index);
+        self.inner.row(0).contains(&index)
+            || self.inner.row(last).contains(&index)
+            || self.inner.column(0).any(|n| *n == index)
+            || self.inner.column(last).any(|n| *n == index)
+    }
+}
+
+/// A coordinate from the input, written like `181, 184`
+pub type Point = Point2<u32>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_generates, assert_part1};
+
+    const EXAMPLE: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9";
+
+    #[test]
+    fn part1_example() {
+        assert_part1(&Day06, EXAMPLE, 17);
+    }
+
+    #[test]
+    fn distance_limited_region_example() {
+        let points = Day06.parse(EXAMPLE).unwrap();
+        let plane = create_plane(&points);
+        assert_eq!(plane.get_distance_limited_region(32, &points), 16);
+    }
+
+    #[test]
+    fn image_of_the_areas() {
+        let points = Day06.parse(EXAMPLE).unwrap();
+        let image = Day06.image(&points).unwrap().unwrap();
+        assert_eq!((image.width(), image.height()), (22, 22));
+        assert_eq!(image.pixel((2, 3)), [255, 255, 255]);
+        assert_eq!(image.pixel((10, 8)), palette(4));
+        assert_eq!(image.pixel((0, 0)), palette(0).map(|c| c / 3));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day06);
+    }
+}
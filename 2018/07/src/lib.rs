@@ -0,0 +1,299 @@
+use std::collections::{BTreeSet, HashMap};
+
+use log::{debug, trace};
+
+use common::{Answer, Error, Params, Parser, Rng, Solution};
+
+/// Most workers there is any use for, one for each step from A to Z
+const MAX_WORKERS: usize = 26;
+
+/// Longest base duration, which keeps the time all 26 steps take together well within an i32
+const MAX_BASE_DURATION: i32 = 1_000_000;
+
+pub struct Day07 {
+    workers: usize,
+    /// Time every step takes on top of its letter's position in the alphabet
+    base_duration: i32
+}
+
+impl Default for Day07 {
+    fn default() -> Day07 {
+        Day07 {
+            workers: 5,
+            base_duration: 60
+        }
+    }
+}
+
+impl Solution for Day07 {
+    type Model = HashMap<char, Step>;
+
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        params.expect_only(&["workers", "base-duration"])?;
+        let expected = format!("a worker count from 1 to {}", MAX_WORKERS);
+        if let Some(workers) = params.get("workers", &expected)? {
+            if !(1..=MAX_WORKERS).contains(&workers) {
+                return Err(Error::parse(&format!("workers={}", workers), &expected));
+            }
+            self.workers = workers;
+        }
+        let expected = format!("a duration from 0 to {} seconds", MAX_BASE_DURATION);
+        if let Some(base_duration) = params.get("base-duration", &expected)? {
+            if !(0..=MAX_BASE_DURATION).contains(&base_duration) {
+                return Err(Error::parse(&format!("base-duration={}", base_duration), &expected));
+            }
+            self.base_duration = base_duration;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<HashMap<char, Step>, Error> {
+        parse_input(input.trim())
+    }
+
+    fn part1(&self, steps: &HashMap<char, Step>) -> Result<Answer, Error> {
+        let (order, _) = schedule(steps.clone(), 1, self.base_duration)?;
+        Ok(order.into_iter().collect::<String>().into())
+    }
+
+    fn part2(&self, steps: &HashMap<char, Step>) -> Result<Answer, Error> {
+        let (_, time) = schedule(steps.clone(), self.workers, self.base_duration)?;
+        Ok(time.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
+/// Generates about `size` instructions between steps lettered from 'A'. The steps are put in a
+/// random order and only ever depend on steps earlier in it, so the instructions never form a
+/// cycle, and every step but the first depends on at least one other.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let count = (size / 2 + 2).min(26);
+    let mut order: Vec<char> = ('A'..='Z').take(count).collect();
+    rng.shuffle(&mut order);
+
+    let mut requirements = BTreeSet::new();
+    for i in 1..count {
+        requirements.insert((order[rng.index(i)], order[i]));
+    }
+    for _ in count..size {
+        let step = 1 + rng.index(count - 1);
+        requirements.insert((order[rng.index(step)], order[step]));
+    }
+
+    let mut lines: Vec<String> = requirements
+        .iter()
+        .map(|(req, step)| format!("Step {} must be finished before step {} can begin.\n", req, step))
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+fn parse_input(input: &str) -> Result<HashMap<char, Step>, Error> {
+    let mut steps: HashMap<char, Step> = HashMap::default();
+
+    for (i, line) in input.lines().enumerate() {
+        let (req, step) = parse_line(line).map_err(|err| err.at_line(i + 1))?;
+        steps.entry(step).or_insert_with(|| Step::new(step)).parents.push(req);
+        steps.entry(req).or_insert_with(|| Step::new(req)).children.push(step);
+    }
+    Ok(steps)
+}
+
+/// The order the steps are done in and the time they take. Steps that wait on each other in a
+/// cycle are never available, which shows as no step to start while every worker is idle.
+fn schedule(mut steps: HashMap<char, Step>, worker_count: usize, base_duration: i32) -> Result<(Vec<char>, i32), Error> {
+    let mut time = 0;
+    let mut workers = vec![Worker {
+        ready_at: 0,
+        working_on: None
+    }; worker_count];
+    let mut done = vec![];
+    while !steps.is_empty() {
+        trace!("Time: {}", time);
+        let mut done_something = false;
+        if let Some(available_step) = get_available_step(&mut steps) {
+            match get_available_worker(&mut workers) {
+                Some(mut worker) => {
+                    debug!("Time: {}, starting work on {} with {:?}", time, available_step.name, worker);
+                    worker.working_on = Some(available_step.name);
+                    worker.ready_at =  time + available_step.time(base_duration);
+                    workers.push(worker);
+                    done_something = true;
+                },
+                None => {
+                    steps.insert(available_step.name, available_step);
+                }
+            }
+        }
+
+        if !done_something {
+            match perform_work(&mut workers) {
+                Some((new_time, step)) => {
+                    done.push(step);
+                    perform_step(step, &mut steps);
+                    time = new_time;
+                },
+                None => {
+                    let mut waiting: Vec<char> = steps.keys().copied().collect();
+                    waiting.sort_unstable();
+                    let waiting: String = waiting.into_iter().collect();
+                    return Err(Error::unsolvable(&format!("steps {} can never start, as their requirements form a cycle", waiting)));
+                }
+            }
+        }
+    }
+    while let Some((new_time, step)) = perform_work(&mut workers) {
+        done.push(step);
+        time = new_time;
+    }
+
+    Ok((done, time))
+}
+
+fn perform_work(workers: &mut Vec<Worker>) -> Option<(i32, char)> {
+    workers.sort_by_key(|w| w.ready_at);
+    trace!("Workers: {:?}", workers);
+    let index = workers.iter().position(|w| w.working_on.is_some())?;
+    let mut worker = workers.remove(index);
+    if worker.working_on.is_some() {
+        debug!("Performed work: {:?}", worker);
+        let work = (worker.ready_at, worker.working_on.expect("Worker has no work to perform!"));
+        worker.working_on = None;
+        workers.push(worker);
+        Some(work)
+    } else {
+        trace!("No work to perform");
+        workers.push(worker);
+        None
+    }
+}
+
+fn get_available_step(steps: &mut HashMap<char, Step>) -> Option<Step> {
+    let c = steps
+        .iter()
+        .filter(|(_, s)| s.parents.is_empty())
+        .map(|(&c, _)| c)
+        .min()?;
+    steps.remove(&c)
+}
+
+fn get_available_worker(workers: &mut Vec<Worker>) -> Option<Worker> {
+    let index = workers.iter().position(|w| w.working_on.is_none())?;
+    Some(workers.remove(index))
+}
+
+fn perform_step(name: char, steps: &mut HashMap<char, Step>) {
+    steps.remove(&name);
+    steps.iter_mut().for_each(|(_, v)| {
+        v.parents.retain(|&c| c != name);
+        v.children.retain(|&c| c != name);
+    });
+}
+
+#[derive(Debug, Clone)]
+struct Worker {
+    ready_at: i32,
+    working_on: Option<char>
+}
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    name: char,
+    parents: Vec<char>,
+    children: Vec<char>,
+}
+
+impl Step {
+    fn new(name: char) -> Step {
+        Step {
+            name,
+            parents: vec![],
+            children: vec![]
+        }
+    }
+
+    fn time(&self, base_duration: i32) -> i32 {
+       (self.name as i32) - 64 + base_duration
+    }
+}
+
+fn parse_line(line: &str) -> Result<(char, char), Error> {
+    const FORMAT: &str = "a step like 'Step C must be finished before step A can begin.'";
+    let mut parser = Parser::new(line, FORMAT);
+    parser.token("Step ")?;
+    let a = parser.char_with(step_name)?;
+    parser.token(" must be finished before step ")?;
+    let b = parser.char_with(step_name)?;
+    parser.token(" can begin.")?;
+    parser.end()?;
+    Ok((a, b))
+}
+
+/// Steps are named by letters, which also give their durations.
+fn step_name(c: char) -> Result<char, Error> {
+    if c.is_ascii_uppercase() {
+        Ok(c)
+    } else {
+        Err(Error::parse(&c.to_string(), "a step named by a letter from A to Z"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_example, assert_generates};
+
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+";
+
+    #[test]
+    fn example() {
+        let day = Day07 {
+            workers: 2,
+            base_duration: 0
+        };
+        assert_example(&day, EXAMPLE, "CABDFE", 15);
+    }
+
+    #[test]
+    fn durations_are_not_negative() {
+        let mut day = Day07::default();
+        assert!(day.configure(&std::iter::once(("base-duration", "-1")).collect()).is_err());
+        assert!(day.configure(&std::iter::once(("base-duration", "0")).collect()).is_ok());
+        assert_eq!(day.base_duration, 0);
+    }
+
+    #[test]
+    fn workers_and_durations_are_bounded() {
+        let mut day = Day07::default();
+        assert!(day.configure(&std::iter::once(("base-duration", "2147483647")).collect()).is_err());
+        assert!(day.configure(&std::iter::once(("workers", "27")).collect()).is_err());
+        assert!(day.configure(&std::iter::once(("workers", "26")).collect()).is_ok());
+        assert!(Day07::default().parse("Step a must be finished before step B can begin.").is_err());
+    }
+
+    #[test]
+    fn cycles_are_unsolvable() {
+        let steps = Day07::default().parse("Step A must be finished before step A can begin.").unwrap();
+        assert!(matches!(Day07::default().part1(&steps), Err(Error::Unsolvable(_))));
+        let steps = Day07::default().parse("Step C must be finished before step A can begin.\n\
+            Step A must be finished before step B can begin.\n\
+            Step B must be finished before step A can begin.").unwrap();
+        assert!(matches!(Day07::default().part2(&steps), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day07::default());
+    }
+}
@@ -0,0 +1,127 @@
+use common::{Answer, Error, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Model = Node;
+
+    fn parse(&self, input: &str) -> Result<Node, Error> {
+        parse_input(input)
+    }
+
+    fn part1(&self, root: &Node) -> Result<Answer, Error> {
+        Ok(sum_meta(root).into())
+    }
+
+    fn part2(&self, root: &Node) -> Result<Answer, Error> {
+        Ok(sum_child_based(root).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut numbers = vec![];
+        generate_node(rng, size.max(1), &mut numbers);
+        let numbers: Vec<String> = numbers.iter().map(u32::to_string).collect();
+        Some(numbers.join(" ") + "\n")
+    }
+}
+
+/// Writes a random subtree of `nodes` nodes. Metadata entries are small enough to often point at
+/// a child, and sometimes past the last one.
+fn generate_node(rng: &mut Rng, nodes: usize, numbers: &mut Vec<u32>) {
+    let mut remaining = nodes - 1;
+    let child_count = if remaining == 0 { 0 } else { rng.range(1..=remaining.min(5) as i64) as usize };
+    let meta_count = rng.range(1..=3) as u32;
+    numbers.push(child_count as u32);
+    numbers.push(meta_count);
+    for child in 0..child_count {
+        let left_for_siblings = child_count - child - 1;
+        let child_nodes = if left_for_siblings == 0 {
+            remaining
+        } else {
+            rng.range(1..=(remaining - left_for_siblings) as i64) as usize
+        };
+        generate_node(rng, child_nodes, numbers);
+        remaining -= child_nodes;
+    }
+    for _ in 0..meta_count {
+        numbers.push(rng.range(1..=9) as u32);
+    }
+}
+
+fn parse_input(input: &str) -> Result<Node, Error> {
+    let mut digits = input.split_whitespace().map(|c| {
+        c.parse().map_err(|_| Error::parse(c, "a number"))
+    }).collect::<Result<Vec<i32>, Error>>()?;
+
+    digits.reverse();
+
+    let root = parse_node(&mut digits)
+        .ok_or_else(|| Error::parse(input.trim(), "a tree of child counts, metadata counts and metadata entries"))?;
+    Ok(root)
+}
+
+fn parse_node(digits: &mut Vec<i32>) -> Option<Node> {
+    let child_count = digits.pop()? as usize;
+    let meta_count = digits.pop()? as usize;
+    let mut children = vec![];
+    let mut meta = vec![];
+
+    for _ in 0..child_count {
+        children.push(parse_node(digits)?);
+    }
+
+    for _ in 0..meta_count {
+        meta.push(digits.pop()?);
+    }
+    Some(Node {
+        child_count,
+        children,
+        meta
+    })
+}
+
+fn sum_meta(root: &Node) -> i32 {
+    root.meta.iter().sum::<i32>() + root.children.iter().fold(0, |acc, r| acc + sum_meta(r))
+}
+
+fn sum_child_based(node: &Node) -> i32 {
+    if node.child_count == 0 {
+        sum_meta(node)
+    } else {
+        node.meta.iter().fold(0, |acc, &idx| {
+            acc + match node.children.get((idx - 1) as usize) {
+                Some(child) => sum_child_based(child),
+                None => 0
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Node {
+    child_count: usize,
+    children: Vec<Node>,
+    meta: Vec<i32>
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_example, assert_generates};
+
+    #[test]
+    fn example() {
+        assert_example(&Day08, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2", 138, 66);
+    }
+
+    #[test]
+    fn unparsable_numbers_are_errors() {
+        assert!(Day08.parse("1 1 0 1 x 1").is_err());
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day08);
+    }
+}
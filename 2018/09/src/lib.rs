@@ -0,0 +1,230 @@
+use common::{Answer, Error, Parser, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Model = (u32, u32);
+
+    fn parse(&self, input: &str) -> Result<(u32, u32), Error> {
+        let line = input.trim().lines().next().unwrap_or_default();
+        parse_line(line).map_err(|err| err.at_line(1))
+    }
+
+    fn part1(&self, &(players, max_marble): &(u32, u32)) -> Result<Answer, Error> {
+        Ok(play(players, max_marble).into())
+    }
+
+    fn part2(&self, &(players, max_marble): &(u32, u32)) -> Result<Answer, Error> {
+        let max_marble = max_marble.checked_mul(100)
+            .ok_or_else(|| Error::unsolvable("the last marble of part 2 overflows a u32"))?;
+        Ok(play(players, max_marble).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size as i64;
+        let players = rng.range(1..=(size + 1).min(500));
+        let max_marble = rng.range(1..=size * 100 + 25);
+        Some(format!("{} players; last marble is worth {} points\n", players, max_marble))
+    }
+}
+
+/// The high score once every marble is played. Scores are summed in 64 bits, as part 2 takes a
+/// player past what fits in 32.
+fn play(players: u32, max_marble: u32) -> u64 {
+    let mut circle = Circle::new();
+    let mut scores = vec![0; players as usize];
+
+    (0..players).cycle().zip(1..=max_marble).for_each(|(p, m)| {
+        if let Some(score) = circle.play(m) {
+            scores[p as usize] += u64::from(score);
+        }
+    });
+
+    *scores.iter().max().unwrap()
+}
+
+fn parse_line(line: &str) -> Result<(u32, u32), Error> {
+    const FORMAT: &str = "play information like '10 players; last marble is worth 1618 points'";
+    let mut parser = Parser::new(line, FORMAT);
+    let column = parser.column();
+    let players = parser.int()?;
+    if players == 0 {
+        return Err(Error::parse("0", &format!("at least one player in {}", FORMAT)).at_column(column));
+    }
+    parser.token(" players; last marble is worth ")?;
+    let max = parser.int()?;
+    // The examples in the puzzle text go on with the high score, which is of no use here
+    parser.token(" points")?;
+    Ok((players, max))
+}
+
+type MarbleId = usize;
+type MarbleValue = u32;
+
+struct Circle {
+    current: MarbleId,
+    marbles: Vec<Marble>
+}
+
+impl Circle {
+    fn new() -> Circle {
+        let marble = Marble::create_empty(0);
+        Circle {
+            current: 0,
+            marbles: vec![marble]
+        }
+    }
+
+    fn play(&mut self, value: MarbleValue) -> Option<u32> {
+        let marble = self.add_marble(value);
+        if value.is_multiple_of(23) {
+            let remove_id = self.counter_clockwise(7);
+            self.remove(remove_id);
+            self.current = self.counter_clockwise(6);
+            Some(value + self.marbles[remove_id].value)
+        } else {
+            let pos = self.clockwise(1);
+            self.insert_after(pos, marble);
+            self.current = marble;
+            None
+        }
+    }
+
+    fn remove(&mut self, index: MarbleId) {
+        let (prev, next) = (self.marbles[index].prev, self.marbles[index].next);
+        self.marbles[prev].next = next;
+        self.marbles[next].prev = prev;
+    }
+
+    fn insert_after(&mut self, index: MarbleId, marble: MarbleId) {
+        let old = self.marbles[index].next;
+        self.marbles[index].next = marble;
+        self.marbles[old].prev = marble;
+        self.marbles[marble].prev = index;
+        self.marbles[marble].next = old;
+    }
+
+    fn add_marble(&mut self, value: MarbleValue) -> MarbleId {
+        let id = self.marbles.len();
+        self.marbles.push(Marble::create_empty(value));
+        id
+    }
+
+    fn clockwise(&mut self, mut count: i32) -> MarbleId {
+        let mut id = self.current;
+        while count > 0 {
+            id = self.marbles[id].next;
+            count -= 1;
+        }
+        id
+    }
+
+    fn counter_clockwise(&mut self, mut count: i32) -> MarbleId {
+        let mut id = self.current;
+        while count > 0 {
+            id = self.marbles[id].prev;
+            count -= 1;
+        }
+        id
+    }
+}
+
+struct Marble {
+    value: MarbleValue,
+    next: MarbleId,
+    prev: MarbleId
+}
+
+impl Marble {
+    fn create_empty(value: MarbleValue) -> Marble {
+        Marble {
+            value,
+            next: 0,
+            prev: 0
+        }
+    }
+}
+
+impl std::fmt::Debug for Circle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut id = self.current;
+        loop {
+            let marble = &self.marbles[id];
+            write!(f, "{}", marble.value)?;
+            id = marble.next;
+            if id == self.current {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{ParseError, Part};
+    use common::testing::{assert_generates, assert_matches_reference, assert_part1};
+
+    #[test]
+    fn part1_examples() {
+        assert_part1(&Day09, "9 players; last marble is worth 25 points", 32);
+        assert_part1(&Day09, "10 players; last marble is worth 1618 points", 8317);
+        assert_part1(&Day09, "13 players; last marble is worth 7999 points", 146373);
+        assert_part1(&Day09, "17 players; last marble is worth 1104 points", 2764);
+        assert_part1(&Day09, "21 players; last marble is worth 6111 points", 54718);
+        assert_part1(&Day09, "30 players; last marble is worth 5807 points", 37305);
+    }
+
+    #[test]
+    fn at_least_one_player() {
+        let err = Day09.parse("0 players; last marble is worth 10 points").err().unwrap();
+        assert!(matches!(err, Error::Parse(ParseError { line: Some(1), column: Some(1), .. })), "{:?}", err);
+    }
+
+    #[test]
+    fn part2_marbles_beyond_a_u32() {
+        let model = Day09.parse("10 players; last marble is worth 50000000 points").unwrap();
+        assert!(matches!(Day09.part2(&model), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day09);
+    }
+
+    /// Plays the game on a plain vector, shifting the marbles on every insert and removal.
+    fn play_naive(players: u32, max_marble: u32) -> u32 {
+        let mut circle = vec![0];
+        let mut current = 0;
+        let mut scores = vec![0; players as usize];
+        for marble in 1..=max_marble {
+            if marble % 23 == 0 {
+                current = (current + circle.len() - 7) % circle.len();
+                scores[((marble - 1) % players) as usize] += marble + circle.remove(current);
+                current %= circle.len();
+            } else {
+                current = (current + 2) % circle.len();
+                if current == 0 {
+                    current = circle.len();
+                }
+                circle.insert(current, marble);
+            }
+        }
+        scores.into_iter().max().unwrap()
+    }
+
+    fn reference(part: Part, &(players, max_marble): &(u32, u32)) -> Option<Answer> {
+        match part {
+            Part::One => Some(play_naive(players, max_marble).into()),
+            Part::Two => None
+        }
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day09, 50, &[1, 5, 20], reference);
+    }
+}
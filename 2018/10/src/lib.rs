@@ -0,0 +1,280 @@
+use std::str::FromStr;
+
+use common::{parse_lines, Answer, Error, Grid, Parser, Part, Point2, Recorder, Rng, Solution};
+
+/// The number of seconds the points are followed before giving up on a message.
+const MAX_STEPS: u32 = 1_000_000;
+
+#[derive(Default)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Model = Vec<Point>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Point>, Error> {
+        let points: Vec<Point> = parse_lines(input.trim())?;
+        if points.is_empty() {
+            return Err(Error::parse(input, "at least one point"));
+        }
+        Ok(points)
+    }
+
+    fn part1(&self, points: &Vec<Point>) -> Result<Answer, Error> {
+        let mut sky = Sky::new(points.clone());
+        sky.converge(&mut Recorder::off())?;
+        Ok(Answer::grid(&sky.message()))
+    }
+
+    fn part2(&self, points: &Vec<Point>) -> Result<Answer, Error> {
+        let mut sky = Sky::new(points.clone());
+        sky.converge(&mut Recorder::off())?;
+        Ok(sky.step.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    /// Both parts watch the points come together into the message.
+    fn animate(&self, _part: Part, points: &Vec<Point>, recorder: &mut Recorder) -> Option<Result<(), Error>> {
+        Some(Sky::new(points.clone()).converge(recorder))
+    }
+}
+
+/// Generates `size` points that light up a random message eight rows high after a random number
+/// of seconds. Each point is moved back from its place in the message along its velocity.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let width = (size as i64 / 3).max(3);
+    let seconds = rng.range(1..=size as i64 * 10 + 10);
+    (0..size.max(1))
+        .map(|_| {
+            let (x, y) = (rng.range(0..=width), rng.range(0..=7));
+            let (vx, vy) = loop {
+                let velocity = (rng.range(-5..=5), rng.range(-5..=5));
+                if velocity != (0, 0) {
+                    break velocity;
+                }
+            };
+            format!(
+                "position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>\n",
+                x - vx * seconds, y - vy * seconds, vx, vy
+            )
+        })
+        .collect()
+}
+
+struct Sky {
+    points: Vec<Point>,
+    step: u32
+}
+
+impl Sky {
+    fn new(points: Vec<Point>) -> Sky {
+        Sky {
+            points,
+            step: 0,
+        }
+    }
+
+    fn step(&mut self) {
+        self.step += 1;
+        for p in &mut self.points {
+            p.position += p.velocity;
+        }
+    }
+
+    fn step_back(&mut self) {
+        self.step -= 1;
+        for p in &mut self.points {
+            p.position -= p.velocity;
+        }
+    }
+
+    /// Moves the points until the area they cover stops shrinking, which is when the message is
+    /// readable. Only the steps where the points fit on a screen are recorded.
+    fn converge(&mut self, recorder: &mut Recorder) -> Result<(), Error> {
+        const SCREEN: (i32, i32) = (200, 60);
+        let mut area = self.bounds().area();
+        while self.step < MAX_STEPS {
+            if !recorder.is_full() {
+                let bounds = self.bounds();
+                if bounds.width() <= SCREEN.0 && bounds.height() <= SCREEN.1 {
+                    recorder.record(|| self.to_string());
+                }
+            }
+            self.step();
+            let next = self.bounds().area();
+            if next >= area {
+                self.step_back();
+                return Ok(());
+            }
+            area = next;
+        }
+        Err(Error::unsolvable(&format!("the points do not come together within {} seconds", MAX_STEPS)))
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::create(&self.points)
+    }
+
+    fn message(&self) -> String {
+        let bounds = self.bounds();
+        let mut lights = Grid::new(bounds.width() as usize, bounds.height() as usize, false);
+
+        self.points.iter().for_each(|p| {
+            let offset = p.position - bounds.min;
+            lights[(offset.x() as usize, offset.y() as usize)] = true;
+        });
+        lights.render(|_, &lit| if lit { '#' } else { '.' })
+    }
+}
+
+impl std::fmt::Display for Sky {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Step: {}\n{}", self.step, self.message())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Point {
+    position: Point2<i32>,
+    velocity: Point2<i32>
+}
+
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Point, Error> {
+        const FORMAT: &str = "a point like 'position=< 9,  1> velocity=< 0,  2>'";
+        let mut parser = Parser::new(line, FORMAT);
+        parser.token("position=<")?;
+        let [x, y] = parser.ints(",")?;
+        parser.token(">")?;
+        parser.spaces();
+        parser.token("velocity=<")?;
+        let [vx, vy] = parser.ints(",")?;
+        parser.token(">")?;
+        parser.end()?;
+        Ok(Point {
+            position: Point2::new(x, y),
+            velocity: Point2::new(vx, vy)
+        })
+    }
+}
+
+impl std::fmt::Debug for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "pos: {} - vel: {}", self.position, self.velocity)
+    }
+}
+
+#[derive(Debug)]
+struct Bounds {
+    min: Point2<i32>,
+    max: Point2<i32>
+}
+
+impl Bounds {
+    fn create(points: &[Point]) -> Bounds {
+        let first = points[0].position;
+        points.iter().fold(Bounds { min: first, max: first }, |bounds, point| {
+            Bounds {
+                min: bounds.min.component_min(&point.position),
+                max: bounds.max.component_max(&point.position)
+            }
+        })
+    }
+
+    fn width(&self) -> i32 {
+        self.max.x() - self.min.x() + 1
+    }
+
+    fn height(&self) -> i32 {
+        self.max.y() - self.min.y() + 1
+    }
+
+    fn area(&self) -> i64 {
+        i64::from(self.width()) * i64::from(self.height())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_example, assert_generates};
+
+    const EXAMPLE: &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>
+";
+
+    const MESSAGE: &str = "\
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###
+";
+
+    #[test]
+    fn example() {
+        assert_example(&Day10, EXAMPLE, Answer::grid(MESSAGE), 3);
+    }
+
+    #[test]
+    fn records_the_steps_on_screen() {
+        let points = Day10.parse(EXAMPLE).unwrap();
+        let mut recorder = Recorder::new(100);
+        Day10.animate(Part::One, &points, &mut recorder).unwrap().unwrap();
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3], format!("Step: 3\n{}", MESSAGE));
+    }
+
+    #[test]
+    fn points_that_never_come_together() {
+        let points = Day10.parse("position=<1, 1> velocity=<0, 0>\n").unwrap();
+        assert_eq!(Day10.part2(&points).unwrap(), 0.into());
+        let points = Day10.parse("position=<0, 0> velocity=<1, 0>\nposition=<0, 0> velocity=<-1, 0>\n").unwrap();
+        assert_eq!(Day10.part2(&points).unwrap(), 0.into());
+        let points = Day10.parse("position=<-5000000, 0> velocity=<1, 0>\nposition=<5000000, 0> velocity=<-1, 0>\n").unwrap();
+        assert!(matches!(Day10.part2(&points), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day10);
+    }
+}
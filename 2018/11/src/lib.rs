@@ -0,0 +1,231 @@
+use fnv::FnvHashMap;
+
+use common::image::heat;
+use common::{Answer, Error, Grid, Image, Params, Rng, Solution};
+
+/// The serial number the solution was written for, used when there is no input file
+const DEFAULT_SERIAL_NUMBER: i32 = 6878;
+
+#[derive(Default)]
+pub struct Day11 {
+    /// Overrides the serial number from the input
+    serial_number: Option<i32>
+}
+
+impl Solution for Day11 {
+    type Model = i32;
+
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        params.expect_only(&["serial"])?;
+        self.serial_number = params.get("serial", "a grid serial number")?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<i32, Error> {
+        match (self.serial_number, input.trim()) {
+            (Some(serial_number), _) => Ok(serial_number),
+            (None, "") => Ok(DEFAULT_SERIAL_NUMBER),
+            (None, input) => input.parse().map_err(|_| Error::parse(input, "a grid serial number").at_line(1))
+        }
+    }
+
+    fn builtin_input(&self) -> bool {
+        true
+    }
+
+    fn part1(&self, &serial_number: &i32) -> Result<Answer, Error> {
+        let mut grid = PowerGrid::new(serial_number);
+
+        let (point, _, _) = find_strongest_square(&mut grid, 3..=3);
+        Ok(format!("{},{}", point.0, point.1).into())
+    }
+
+    fn part2(&self, &serial_number: &i32) -> Result<Answer, Error> {
+        let mut grid = PowerGrid::new(serial_number);
+
+        let (point, size, _) = find_strongest_square(&mut grid, 1..=15);
+        Ok(format!("{},{},{}", point.0, point.1, size).into())
+    }
+
+    /// The input is a single serial number, so `size` is not used.
+    fn generate(&self, rng: &mut Rng, _size: usize) -> Option<String> {
+        Some(format!("{}\n", rng.range(1..=9999)))
+    }
+
+    /// The power level of every fuel cell, from -5 in the darkest colour to 4 in the brightest.
+    fn image(&self, &serial_number: &i32) -> Option<Result<Image, Error>> {
+        let grid = PowerGrid::new(serial_number);
+        Some(Ok(Image::from_grid(&grid.values, 2, |_, &level| heat(f64::from(level + 5) / 9.0))))
+    }
+}
+
+/// Finds the square with the highest total power level among the given square sizes.
+///
+/// Every size below the largest one is calculated as well, since the grid builds larger squares
+/// from the cached smaller ones.
+fn find_strongest_square(grid: &mut PowerGrid, sizes: std::ops::RangeInclusive<i32>) -> ((i32, i32), i32, i32) {
+    let mut sum = 0;
+    let mut point = (0, 0);
+    let mut size = 0;
+    for s in 1..=*sizes.end() {
+        for x in 1..=(300 - s + 1) {
+            for y in 1..=(300 - s + 1) {
+                match grid.get_square_level(x, y, s) {
+                    Some(level) if level > sum && sizes.contains(&s) => {
+                        sum = level;
+                        point = (x, y);
+                        size = s;
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+    (point, size, sum)
+}
+
+struct PowerGrid {
+    values: Grid<i32>,
+    cache: FnvHashMap<(i32, i32, i32), i32>,
+}
+
+impl PowerGrid {
+    fn new(serial_number: i32) -> PowerGrid {
+        PowerGrid {
+            values: Grid::from_fn(300, 300, |(x, y)| {
+                power_level((x + 1) as i32, (y + 1) as i32, serial_number)
+            }),
+            cache: FnvHashMap::default()
+        }
+    }
+
+    fn get_square_level(&mut self, left: i32, top: i32, size: i32) -> Option<i32> {
+        if left > self.values.width() as i32 - (size - 1) || top > self.values.height() as i32 - (size - 1) {
+            return None;
+        }
+
+        let mut sum = 0;
+        for x in left..(left + size) {
+            sum += self.get_power_level(x, top);
+        }
+        if size > 1 {
+            for y in (top + 1)..(top + size) {
+                sum += self.get_power_level(left, y);
+            }
+            // The smaller square is usually cached, from searching the sizes in order
+            sum += match self.cache.get(&(left + 1, top + 1, size - 1)) {
+                Some(&smaller) => smaller,
+                None => self.get_square_level(left + 1, top + 1, size - 1)?
+            };
+        }
+        self.cache.insert((left, top, size), sum);
+        Some(sum)
+    }
+
+    /// The power level of a cell, with the puzzle's coordinates starting at 1.
+    fn get_power_level(&self, x: i32, y: i32) -> i32 {
+        self.values[((x - 1) as usize, (y - 1) as usize)]
+    }
+}
+
+fn power_level(x: i32, y: i32, serial_number: i32) -> i32 {
+    let rack_id = x + 10;
+    let mut power = y * rack_id;
+    power += serial_number;
+    power *= rack_id;
+    if power < 100 {
+        power = 0;
+    } else {
+        power = ((power as f32)/ 100f32).floor() as i32;
+    }
+    power %= 10;
+    power - 5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use common::testing::{assert_generates, assert_matches_reference, assert_part1, assert_part2};
+
+    #[test]
+    fn power_level_examples() {
+        assert_eq!(power_level(3, 5, 8), 4);
+        assert_eq!(power_level(122, 79, 57), -5);
+        assert_eq!(power_level(217, 196, 39), 0);
+        assert_eq!(power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_part1(&Day11::default(), "18", "33,45");
+        assert_part1(&Day11::default(), "42", "21,61");
+    }
+
+    #[test]
+    fn part2_example() {
+        // The other example's best square is 16 wide, beyond the sizes part 2 searches
+        assert_part2(&Day11::default(), "42", "232,251,12");
+    }
+
+    #[test]
+    fn image_of_the_power_levels() {
+        let image = Day11::default().image(&8).unwrap().unwrap();
+        assert_eq!((image.width(), image.height()), (600, 600));
+        assert_eq!(image.pixel((4, 8)), heat(1.0));
+    }
+
+    #[test]
+    fn squares_without_the_smaller_ones_cached() {
+        let mut grid = PowerGrid::new(18);
+        assert_eq!(grid.get_square_level(33, 45, 3), Some(29));
+        assert_eq!(grid.get_square_level(90, 269, 16), Some(113));
+        assert_eq!(grid.get_square_level(290, 290, 12), None);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day11::default());
+    }
+
+    /// Sums each square from a table of the sums of all rectangles that start in the top left
+    /// corner, searching the squares in the same order as the solution.
+    fn reference(part: Part, &serial_number: &i32) -> Option<Answer> {
+        let mut table = vec![vec![0i64; 301]; 301];
+        for x in 1..=300 {
+            for y in 1..=300 {
+                let rack_id = x + 10;
+                let power = (((y * rack_id + i64::from(serial_number)) * rack_id) / 100) % 10 - 5;
+                table[x as usize][y as usize] = power + table[x as usize - 1][y as usize]
+                    + table[x as usize][y as usize - 1] - table[x as usize - 1][y as usize - 1];
+            }
+        }
+
+        let sizes = match part {
+            Part::One => 3..=3,
+            Part::Two => 1..=15
+        };
+        let mut best: Option<(i64, usize, usize, usize)> = None;
+        for size in sizes {
+            for x in 1..=(301 - size) {
+                for y in 1..=(301 - size) {
+                    let (right, bottom) = (x + size - 1, y + size - 1);
+                    let level = table[right][bottom] - table[x - 1][bottom] - table[right][y - 1] + table[x - 1][y - 1];
+                    if best.is_none_or(|(best, ..)| level > best) {
+                        best = Some((level, x, y, size));
+                    }
+                }
+            }
+        }
+        let (_, x, y, size) = best?;
+        Some(match part {
+            Part::One => format!("{},{}", x, y).into(),
+            Part::Two => format!("{},{},{}", x, y, size).into()
+        })
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day11::default(), 2, &[1], reference);
+    }
+}
@@ -0,0 +1,444 @@
+use std::str::FromStr;
+use std::fmt;
+
+use common::image::heat;
+use common::{Answer, Error, Grid, Image, Parser, Part, Recorder, Rng, Solution};
+
+/// The number of generations part 2 simulates while waiting for the pots to settle.
+const MAX_GENERATIONS: usize = 1_000;
+
+#[derive(Default)]
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Model = (State, Vec<Pattern>);
+
+    fn parse(&self, input: &str) -> Result<(State, Vec<Pattern>), Error> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (state, patterns): &(State, Vec<Pattern>)) -> Result<Answer, Error> {
+        Ok(run(state.clone(), 20, patterns, &mut Recorder::off()).into())
+    }
+
+    fn part2(&self, (state, patterns): &(State, Vec<Pattern>)) -> Result<Answer, Error> {
+        Ok(run_long(state.clone(), 50_000_000_000, patterns, &mut Recorder::off())?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    /// Part 2 is watched until the pots settle, as the generations after that only shift.
+    fn animate(&self, part: Part, (state, patterns): &(State, Vec<Pattern>), recorder: &mut Recorder) -> Option<Result<(), Error>> {
+        match part {
+            Part::One => {
+                run(state.clone(), 20, patterns, recorder);
+                Some(Ok(()))
+            },
+            Part::Two => Some(run_long(state.clone(), 50_000_000_000, patterns, recorder).map(|_| ()))
+        }
+    }
+    fn image(&self, (state, patterns): &(State, Vec<Pattern>)) -> Option<Result<Image, Error>> {
+        let diagram = space_time(state, patterns, 200);
+        Some(Ok(Image::from_grid(&diagram, 3, |_, &pot| {
+            match pot {
+                Pot::Flower => heat(0.85),
+                Pot::Empty => heat(0.05)
+            }
+        })))
+    }
+}
+
+/// Generates an initial state of `size` pots followed by a result for each of the 32 patterns.
+/// A pot only has a flower when the pot `shift` places to its left had one, so every flower
+/// either moves along by the same step or withers. Once a generation passes where none wither,
+/// the pots only shift, which part 2 relies on to skip ahead. At least one flower withers in
+/// every generation before that, so the pots are capped to settle within `MAX_GENERATIONS`.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let pot = |rng: &mut Rng| if rng.chance(1, 2) { '#' } else { '.' };
+    let state: String = (0..size.clamp(1, MAX_GENERATIONS)).map(|_| pot(rng)).collect();
+    let shift = rng.range(-1..=1);
+    let mut patterns: Vec<String> = (0..32)
+        .map(|n: u32| {
+            let pattern: String = (0..5).rev().map(|bit| if n & (1 << bit) == 0 { '.' } else { '#' }).collect();
+            let survives = pattern.as_bytes()[(2 - shift) as usize] == b'#' && rng.chance(3, 4);
+            format!("{} => {}\n", pattern, if survives { '#' } else { '.' })
+        })
+        .collect();
+    rng.shuffle(&mut patterns);
+    format!("initial state: {}\n\n{}", state, patterns.concat())
+}
+
+fn parse_input(input: &str) -> Result<(State, Vec<Pattern>), Error> {
+    let mut lines = input.trim().lines();
+
+    let state: State = lines.next().unwrap_or_default().parse().map_err(|err: Error| err.at_line(1))?;
+
+    let mut patterns: Vec<Pattern> = vec![];
+
+    for (i, line) in lines.enumerate() {
+        if !line.is_empty() {
+            let pattern: Pattern = line.parse().map_err(|err: Error| err.at_line(i + 2))?;
+            patterns.push(pattern);
+        }
+    }
+
+    Ok((state, patterns))
+}
+
+fn run(mut state: State, generations: usize, patterns: &[Pattern], recorder: &mut Recorder) -> i32 {
+    let first = state.left;
+    recorder.record(|| state.row(first));
+    for _ in 0..generations {
+        state = state.next_generation(patterns);
+        recorder.record(|| state.row(first));
+    }
+    state.count()
+}
+
+/// Runs the given number of generations, skipping ahead once the pots have settled into a
+/// pattern that only shifts position from one generation to the next. Pots that have not settled
+/// after `MAX_GENERATIONS` are given up on.
+fn run_long(mut state: State, generations: usize, patterns: &[Pattern], recorder: &mut Recorder) -> Result<i64, Error> {
+    let first = state.left;
+    recorder.record(|| state.row(first));
+    for generation in 0..generations {
+        if generation == MAX_GENERATIONS {
+            return Err(Error::unsolvable(&format!("the pots do not settle within {} generations", MAX_GENERATIONS)));
+        }
+        let next = state.clone().next_generation(patterns);
+        recorder.record(|| next.row(first));
+        if next.pots == state.pots {
+            let delta = i64::from(next.count() - state.count());
+            return Ok(i64::from(state.count()) + delta * (generations - generation) as i64);
+        }
+        state = next;
+    }
+    Ok(i64::from(state.count()))
+}
+
+/// The pots of every generation, one row each, until the pots settle or for at most `limit`
+/// generations. Columns are the same pot in every row.
+fn space_time(state: &State, patterns: &[Pattern], limit: usize) -> Grid<Pot> {
+    let mut generations = vec![state.clone()];
+    while generations.len() < limit.max(1) {
+        let current = &generations[generations.len() - 1];
+        let next = current.clone().next_generation(patterns);
+        let settled = next.pots == current.pots;
+        generations.push(next);
+        if settled {
+            break;
+        }
+    }
+    let left = generations.iter().map(|state| state.left).min().unwrap_or(0);
+    let right = generations.iter().map(|state| state.left + state.pots.len() as i32).max().unwrap_or(0);
+    Grid::from_fn((right - left) as usize, generations.len(), |(x, y)| {
+        let state = &generations[y];
+        state.get(x as i32 + left - state.left)
+    })
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Pattern {
+    pattern: [Pot; 5],
+    result: Pot
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Pattern, Error> {
+        let mut parser = Parser::new(line, "a pattern like '..#.. => #'");
+        let mut pattern = [Pot::Empty; 5];
+        for pot in pattern.iter_mut() {
+            *pot = parser.char_with(Pot::from_char)?;
+        }
+        parser.token(" => ")?;
+        let result = parser.char_with(Pot::from_char)?;
+        parser.end()?;
+        Ok(Pattern {
+            pattern,
+            result
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Pot {
+    Flower,
+    Empty
+}
+
+impl Pot {
+    fn from_char(c: char) -> Result<Pot, Error> {
+        match c {
+            '.' => Ok(Pot::Empty),
+            '#' => Ok(Pot::Flower),
+            _ => Err(Error::parse(&c.to_string(), "a pot, either '.' or '#'"))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct State {
+    pots: Vec<Pot>,
+    generation: usize,
+    left: i32
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            pots: vec![],
+            generation: 0,
+            left: 0
+        }
+    }
+
+    fn add_pot(&mut self, pot: Pot) {
+        self.pots.push(pot);
+    }
+
+    fn get(&self, pos: i32) -> Pot {
+        if pos < 0 {
+            Pot::Empty
+        } else {
+            match self.pots.get(pos as usize) {
+                Some(pot) => *pot,
+                None => Pot::Empty
+            }
+        }
+    }
+
+    fn get_five(&self, pos: i32) -> [Pot; 5] {
+        let mut pots = [Pot::Empty; 5];
+        ((pos - 2)..=(pos + 2)).enumerate().for_each(|(i, p)| {
+            pots[i] = self.get(p);
+        });
+        pots
+    }
+
+    /// The generation and the pots from pot number `first` on, so rows of different generations
+    /// line up. Flowers left of `first` are cut off.
+    fn row(&self, first: i32) -> String {
+        let pots: String = (first..self.left + self.pots.len() as i32)
+            .map(|pos| match self.get(pos - self.left) {
+                Pot::Empty => '.',
+                Pot::Flower => '#'
+            })
+            .collect();
+        format!("{:>3}: {}", self.generation, pots)
+    }
+
+    fn count(&self) -> i32 {
+        self.pots.iter().enumerate().filter_map(|(i, p)| {
+            match p {
+                Pot::Flower => {
+                    Some(i as i32 + self.left)
+                },
+                Pot::Empty => None
+            }
+        }).sum()
+    }
+
+    fn next_generation(self, patterns: &[Pattern]) -> State {
+        let mut state = State::new();
+        state.generation = self.generation + 1;
+        state.left = self.left - 2;
+        let mut id = -2;
+        loop {
+            let pots = self.get_five(id);
+            let mut matched = false;
+            for pattern in patterns {
+                if pots == pattern.pattern {
+                    state.pots.push(pattern.result);
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                state.pots.push(Pot::Empty);
+            }
+            id += 1;
+            if id > self.pots.len() as i32 + 3 {
+                break;
+            }
+        }
+        state.trim_left();
+        state.trim_right();
+        state
+    }
+
+    /// Without any flowers there are no pots left, so a row that has died out stays the same.
+    fn trim_left(&mut self) {
+        let pos = self.pots.iter().position(|p| *p == Pot::Flower);
+        if let Some(pos) = pos {
+            if pos > 3 {
+                for _ in 0..(pos - 3) {
+                    let _ = self.pots.remove(0);
+                    self.left += 1;
+                }
+            } else if pos < 3 {
+                for _ in 0..(3 - pos) {
+                    self.pots.insert(0, Pot::Empty);
+                    self.left -= 1;
+                }
+            }
+        } else {
+            self.pots.clear();
+        }
+    }
+
+    fn trim_right(&mut self) {
+        let pos = self.pots.iter().rev().position(|p| *p == Pot::Flower);
+        if let Some(pos) = pos {
+            if pos > 3 {
+                for _ in 0..(pos - 3) {
+                    let _ = self.pots.pop();
+                }
+            } else if pos < 3 {
+                for _ in 0..(3 - pos) {
+                    self.pots.push(Pot::Empty);
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for State {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<State, Error> {
+        let mut parser = Parser::new(line, "an initial state like 'initial state: #..#.#..##'");
+        parser.token("initial state: ")?;
+        let mut state = State::new();
+        loop {
+            state.add_pot(parser.char_with(Pot::from_char)?);
+            if parser.is_empty() {
+                break;
+            }
+        }
+        state.trim_left();
+        state.trim_right();
+        Ok(state)
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = format!("{}: ", self.generation);
+        self.pots.iter().for_each(|pot| {
+            match pot {
+                Pot::Empty => buf.push('.'),
+                Pot::Flower => buf.push('#')
+            }
+        });
+        write!(f, "{}", buf)
+
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use common::testing::{assert_generates, assert_matches_reference, assert_part1, assert_solves_generated};
+
+    const EXAMPLE: &str = "\
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #
+";
+
+    #[test]
+    fn part1_example() {
+        assert_part1(&Day12, EXAMPLE, 325);
+    }
+
+    #[test]
+    fn records_lined_up_generations() {
+        let model = Day12.parse(EXAMPLE).unwrap();
+        let mut recorder = Recorder::new(100);
+        Day12.animate(Part::One, &model, &mut recorder).unwrap().unwrap();
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 21);
+        assert_eq!(frames[1].trim_end_matches('.'), "  1: ...#...#....#.....#..#..#..#");
+        assert_eq!(frames[20].trim_end_matches('.'), " 20: .#....##....#####...#######....#.#..##");
+    }
+
+    #[test]
+    fn space_time_diagram() {
+        let (state, patterns) = Day12.parse(EXAMPLE).unwrap();
+        let diagram = space_time(&state, &patterns, 21);
+        assert_eq!(diagram.height(), 21);
+        let first = diagram.row(0).iter().position(|&pot| pot == Pot::Flower);
+        let last = diagram.row(20).iter().position(|&pot| pot == Pot::Flower);
+        // The first flower starts at pot 0 and ends up at pot -2
+        assert_eq!(first.map(|x| x - 2), last);
+    }
+
+    #[test]
+    fn pots_that_never_settle() {
+        let model = Day12.parse("initial state: #\n\n..#.. => #\n....# => #\n").unwrap();
+        assert!(matches!(Day12.part2(&model), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day12);
+    }
+
+    #[test]
+    fn generated_inputs_settle() {
+        assert_solves_generated(&Day12, &[1, 20, 100]);
+    }
+
+    /// Keeps the positions of the pots with plants in a set, and looks every pot that could
+    /// change up in the patterns, one generation at a time. Part 2 is out of reach this way.
+    fn reference(part: Part, (state, patterns): &(State, Vec<Pattern>)) -> Option<Answer> {
+        if part == Part::Two {
+            return None;
+        }
+        let mut plants: HashSet<i32> = state.pots.iter().enumerate()
+            .filter(|&(_, &pot)| pot == Pot::Flower)
+            .map(|(i, _)| i as i32 + state.left)
+            .collect();
+        for _ in 0..20 {
+            let (min, max) = match (plants.iter().min(), plants.iter().max()) {
+                (Some(&min), Some(&max)) => (min, max),
+                _ => break
+            };
+            plants = (min - 2..=max + 2)
+                .filter(|&pos| {
+                    let surroundings: Vec<Pot> = (pos - 2..=pos + 2)
+                        .map(|p| if plants.contains(&p) { Pot::Flower } else { Pot::Empty })
+                        .collect();
+                    patterns.iter()
+                        .find(|pattern| pattern.pattern[..] == surroundings[..])
+                        .is_some_and(|pattern| pattern.result == Pot::Flower)
+                })
+                .collect();
+        }
+        Some(plants.iter().sum::<i32>().into())
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day12, 50, &[1, 10, 50], reference);
+    }
+}
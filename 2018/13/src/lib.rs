@@ -0,0 +1,531 @@
+use std::str::FromStr;
+use std::fmt;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use log::{debug, trace};
+
+use common::{Answer, Error, Grid, Part, Point2, ReadingOrder, Recorder, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Model = System;
+
+    fn parse(&self, input: &str) -> Result<System, Error> {
+        let system: System = input.parse()?;
+        if system.count_carts() < 2 {
+            return Err(Error::parse(input, "a track map with at least two carts"));
+        }
+        Ok(system)
+    }
+
+    fn part1(&self, system: &System) -> Result<Answer, Error> {
+        let (collisions, _) = run(system.clone(), &mut Recorder::off());
+        let collision = collisions.first().ok_or_else(|| Error::unsolvable("no carts collided"))?;
+        Ok(collision.to_string().into())
+    }
+
+    fn part2(&self, system: &System) -> Result<Answer, Error> {
+        let (_, left) = run(system.clone(), &mut Recorder::off());
+        match left.as_slice() {
+            [last] => Ok(last.to_string().into()),
+            [] => Err(Error::unsolvable("no carts left after the last collision")),
+            _ => Err(Error::unsolvable("the carts that are left never collide"))
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    /// Both parts watch the same race, which runs until a single cart is left.
+    fn animate(&self, _part: Part, system: &System, recorder: &mut Recorder) -> Option<Result<(), Error>> {
+        run(system.clone(), recorder);
+        Some(Ok(()))
+    }
+}
+
+/// Generates a single loop of track about `size` long, set somewhere in an empty map, with an
+/// odd number of carts on it. One more cart goes one way around the loop than the other, and
+/// carts going the same way are at least two squares apart so they never catch up with each
+/// other. Carts are then sure to collide until a single one is left.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let loop_width = rng.range(3..=(size as i64 / 4).max(3) + 3) as usize;
+    let loop_height = rng.range(3..=(size as i64 / 4).max(3) + 3) as usize;
+    let (left, top) = (rng.index(4), rng.index(4));
+    let (right, bottom) = (left + loop_width - 1, top + loop_height - 1);
+    let mut map = Grid::new(right + 1 + rng.index(4), bottom + 1 + rng.index(4), ' ');
+
+    // Walks the loop clockwise, keeping the straight pieces with the way a clockwise and a
+    // counter clockwise cart drive along them
+    let mut straights = vec![vec![], vec![]];
+    let mut step = 0;
+    let mut track = |map: &mut Grid<char>, position, piece, carts: Option<(char, char)>| {
+        map[position] = piece;
+        if let Some((clockwise, counter_clockwise)) = carts {
+            straights[step % 2].push((position, clockwise, counter_clockwise));
+        }
+        step += 1;
+    };
+    track(&mut map, (left, top), '/', None);
+    for x in left + 1..right {
+        track(&mut map, (x, top), '-', Some(('>', '<')));
+    }
+    track(&mut map, (right, top), '\\', None);
+    for y in top + 1..bottom {
+        track(&mut map, (right, y), '|', Some(('v', '^')));
+    }
+    track(&mut map, (right, bottom), '/', None);
+    for x in (left + 1..right).rev() {
+        track(&mut map, (x, bottom), '-', Some(('<', '>')));
+    }
+    track(&mut map, (left, bottom), '\\', None);
+    for y in (top + 1..bottom).rev() {
+        track(&mut map, (left, y), '|', Some(('^', 'v')));
+    }
+
+    // Squares every other step around the loop are never next to each other
+    let mut straights = straights.swap_remove(if straights[0].len() > straights[1].len() { 0 } else { 1 });
+    rng.shuffle(&mut straights);
+    let pairs = rng.range(1..=(straights.len() as i64 - 1) / 2) as usize;
+    let clockwise_extra = rng.chance(1, 2);
+    for (i, &(position, clockwise, counter_clockwise)) in straights.iter().take(pairs * 2 + 1).enumerate() {
+        let is_clockwise = if i == pairs * 2 { clockwise_extra } else { i % 2 == 0 };
+        map[position] = if is_clockwise { clockwise } else { counter_clockwise };
+    }
+    map.render(|_, &c| c)
+}
+
+/// Moves the carts until at most one is left, or until the carts are back where they were after
+/// the last collision, after which they would go round the same way forever. Returns the positions
+/// of the collisions in the order they happened and the positions of the carts left.
+fn run(mut system: System, recorder: &mut Recorder) -> (Vec<Pos>, Vec<Pos>) {
+    let mut collision_positions = vec![];
+    let mut tick = 0;
+    // Every state of the carts follows from a single earlier one, so carts that never collide
+    // again come back to the state after the last collision before repeating any other
+    let mut since_collision = system.state();
+    loop {
+        recorder.record(|| format!("Tick: {}\n{}", tick, system));
+        let res = system.tick();
+        tick += 1;
+        trace!("Tick {}: {} carts left", tick, system.count_carts());
+        if let Err(collisions) = res {
+            for (pos, carts) in &collisions {
+                debug!("Tick {}: carts {:?} crashed at {}", tick, carts, pos);
+                collision_positions.push(*pos);
+                for cart in carts {
+                    system.remove_cart(*cart);
+                }
+            }
+            since_collision = system.state();
+        } else if system.state() == since_collision {
+            debug!("Tick {}: the {} carts left go round without ever colliding", tick, system.count_carts());
+            break;
+        }
+        if system.count_carts() <= 1 {
+            break;
+        }
+    }
+    recorder.record(|| format!("Tick: {}\n{}", tick, system));
+
+    (collision_positions, system.carts.iter().map(|cart| cart.pos).collect())
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+impl Direction {
+    fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Right => '>',
+            Direction::Left => '<',
+        }
+    }
+
+    fn step(self) -> Pos {
+        match self {
+            Direction::Up => Pos::new(0, -1),
+            Direction::Down => Pos::new(0, 1),
+            Direction::Left => Pos::new(-1, 0),
+            Direction::Right => Pos::new(1, 0)
+        }
+    }
+
+    /// The way a cart goes on after driving onto `track` this way, taking `turn` if it is an
+    /// intersection.
+    fn follow(self, track: Track, turn: Turn) -> Direction {
+        let mut direction = self;
+        match track {
+            Track::RightDown => {
+                direction = match self {
+                    Direction::Right => Direction::Down,
+                    Direction::Left => Direction::Up,
+                    Direction::Down => Direction::Right,
+                    Direction::Up => Direction::Left,
+                }
+            },
+            Track::LeftUp => {
+                direction = match self {
+                    Direction::Right => Direction::Up,
+                    Direction::Left => Direction::Down,
+                    Direction::Down => Direction::Left,
+                    Direction::Up => Direction::Right,
+                }
+            },
+            Track::Intersection => direction.turn(turn),
+            _ => {}
+        }
+        direction
+    }
+
+    fn turn(&mut self, turn: Turn) {
+        match turn {
+            Turn::Left => {
+                *self = match *self {
+                    Direction::Up => Direction::Left,
+                    Direction::Down => Direction::Right,
+                    Direction::Left => Direction::Down,
+                    Direction::Right => Direction::Up,
+                }
+            },
+            Turn::Right => {
+                *self = match *self {
+                    Direction::Up => Direction::Right,
+                    Direction::Down => Direction::Left,
+                    Direction::Left => Direction::Up,
+                    Direction::Right => Direction::Down,
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Turn {
+    Left,
+    Straight,
+    Right,
+}
+
+/// Carts move in reading order, so positions sort top to bottom and then left to right
+type Pos = Point2<i32, ReadingOrder>;
+type CartId = u32;
+
+#[derive(Debug, Clone)]
+struct Cart {
+    id: CartId,
+    pos: Pos,
+    last_turn: Turn,
+    direction: Direction
+}
+
+impl Cart {
+    fn get_next_turn(&self) -> Turn {
+        match self.last_turn {
+            Turn::Right => Turn::Left,
+            Turn::Left => Turn::Straight,
+            Turn::Straight => Turn::Right,
+        }
+    }
+}
+
+impl PartialOrd for Cart {
+    fn partial_cmp(&self, other: &Cart) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cart {
+    fn cmp(&self, other: &Cart) -> Ordering {
+        self.pos.cmp(&other.pos)
+    }
+}
+
+impl PartialEq for Cart {
+    fn eq(&self, other: &Cart) -> bool {
+        self.pos == other.pos && self.direction == other.direction
+    }
+}
+
+impl Eq for Cart {}
+
+#[derive(Clone)]
+pub struct System {
+    grid: Grid<Track>,
+    carts: Vec<Cart>
+}
+
+impl System {
+    fn get_cart_at(&self, pos: Pos) -> Option<&Cart> {
+        self.carts.iter().find(|c| c.pos == pos)
+    }
+
+    /// Moves every cart one step. Carts that crash are out of the race at once, so they neither
+    /// move on nor get hit by carts later in the same tick.
+    fn tick(&mut self) -> Result<(), Vec<(Pos, Vec<CartId>)>>{
+        self.carts.sort();
+        let mut colliding_carts = vec![];
+        let mut crashed = vec![];
+        for i in 0..self.carts.len() {
+            if crashed.contains(&self.carts[i].id) {
+                continue;
+            }
+            self.move_cart(i);
+            let colliding = self.carts
+                .iter()
+                .filter_map(|c| {
+                    if c.pos == self.carts[i].pos && !crashed.contains(&c.id) {
+                        Some(c.id)
+                    } else {
+                        None
+                    }
+                }).collect::<Vec<_>>();
+            if colliding.len() > 1 {
+                crashed.extend(&colliding);
+                colliding_carts.push((self.carts[i].pos, colliding));
+            }
+        }
+        if !colliding_carts.is_empty() {
+            Err(colliding_carts)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn remove_cart(&mut self, id: CartId) {
+        self.carts.retain(|c| c.id != id)
+    }
+
+    fn count_carts(&self) -> usize {
+        self.carts.len()
+    }
+
+    fn move_cart(&mut self, i: usize) {
+        let cart = &mut self.carts[i];
+        cart.pos += cart.direction.step();
+        let track = self.grid[(cart.pos.x() as usize, cart.pos.y() as usize)];
+        if let Track::Intersection = track {
+            cart.last_turn = cart.get_next_turn();
+        }
+        cart.direction = cart.direction.follow(track, cart.last_turn);
+    }
+
+    /// Where every cart is, which way it goes and how it last turned, in the order of the ids.
+    fn state(&self) -> Vec<(CartId, Pos, Direction, Turn)> {
+        let mut state: Vec<_> = self.carts.iter().map(|cart| (cart.id, cart.pos, cart.direction, cart.last_turn)).collect();
+        state.sort_unstable_by_key(|&(id, ..)| id);
+        state
+    }
+
+    fn track_at(&self, pos: Pos) -> Option<Track> {
+        let position = (usize::try_from(pos.x()).ok()?, usize::try_from(pos.y()).ok()?);
+        self.grid.get(position).copied()
+    }
+
+    /// Checks that wherever a cart goes, and whichever way it turns at intersections, it stays
+    /// on the track. Errors are at the cart that can run off it.
+    fn check_track(&self) -> Result<(), Error> {
+        let mut seen = HashSet::new();
+        for cart in &self.carts {
+            let mut stack = vec![(cart.pos, cart.direction)];
+            while let Some((pos, direction)) = stack.pop() {
+                if !seen.insert((pos, direction)) {
+                    continue;
+                }
+                let next = pos + direction.step();
+                let track = self.track_at(next).filter(|track| track.accepts(direction)).ok_or_else(|| {
+                    Error::parse(&cart.direction.arrow().to_string(), "a cart on a track that does not run off")
+                        .at_line(cart.pos.y() as usize + 1)
+                        .at_column(cart.pos.x() as usize + 1)
+                })?;
+                // Only intersections tell the turns apart
+                for turn in [Turn::Left, Turn::Straight, Turn::Right] {
+                    stack.push((next, direction.follow(track, turn)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let buf = self.grid.render(|(x, y), track| {
+            if let Some(cart) = self.get_cart_at(Pos::new(x as i32, y as i32)) {
+                cart.direction.arrow()
+            } else {
+                match track {
+                    Track::Empty => ' ',
+                    Track::Vertical => '|',
+                    Track::Horizontal => '-',
+                    Track::LeftUp => '/',
+                    Track::RightDown => '\\',
+                    Track::Intersection => '+',
+                }
+            }
+        });
+        write!(f, "{}", buf)
+
+    }
+}
+
+
+impl FromStr for System {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<System, Error> {
+        let mut carts = vec![];
+        let mut add_cart = |(x, y): (usize, usize), direction| {
+            carts.push(Cart {
+                id: carts.len() as u32,
+                pos: Pos::new(x as i32, y as i32),
+                last_turn: Turn::Right,
+                direction
+            });
+        };
+
+        let grid = Grid::parse(input, |position, c| {
+            match c {
+                ' ' => Ok(Track::Empty),
+                '|' => Ok(Track::Vertical),
+                'v' => {
+                    add_cart(position, Direction::Down);
+                    Ok(Track::Vertical)
+                }
+                '^' => {
+                    add_cart(position, Direction::Up);
+                    Ok(Track::Vertical)
+                }
+                '-' => Ok(Track::Horizontal),
+                '<' => {
+                    add_cart(position, Direction::Left);
+                    Ok(Track::Horizontal)
+                },
+                '>' => {
+                    add_cart(position, Direction::Right);
+                    Ok(Track::Horizontal)
+                },
+                '/' => Ok(Track::LeftUp),
+                '\\' => Ok(Track::RightDown),
+                '+' => Ok(Track::Intersection),
+                _ => Err(Error::parse(&c.to_string(), "a track or a cart"))
+            }
+        })?;
+
+        let system = System {
+            grid,
+            carts,
+        };
+        system.check_track()?;
+        Ok(system)
+    }
+}
+
+
+#[derive(Debug, Copy, Clone)]
+enum Track {
+    Empty,
+    Intersection,
+    Horizontal,
+    Vertical,
+    RightDown, // '\'
+    LeftUp, // '/;
+}
+
+impl Track {
+    /// Whether a cart going this way can drive onto the track.
+    fn accepts(self, direction: Direction) -> bool {
+        match self {
+            Track::Empty => false,
+            Track::Horizontal => matches!(direction, Direction::Left | Direction::Right),
+            Track::Vertical => matches!(direction, Direction::Up | Direction::Down),
+            _ => true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::ParseError;
+    use common::testing::{assert_generates, assert_part1, assert_part2};
+
+    // Written line by line, since the maps depend on their leading and trailing spaces
+    const CRASH_EXAMPLE: &str = concat!(
+        "/->-\\        \n",
+        "|   |  /----\\\n",
+        "| /-+--+-\\  |\n",
+        "| | |  | v  |\n",
+        "\\-+-/  \\-+--/\n",
+        "  \\------/   \n",
+    );
+
+    const LAST_CART_EXAMPLE: &str = concat!(
+        "/>-<\\  \n",
+        "|   |  \n",
+        "| /<+-\\\n",
+        "| | | v\n",
+        "\\>+</ |\n",
+        "  |   ^\n",
+        "  \\<->/\n",
+    );
+
+    #[test]
+    fn part1_example() {
+        assert_part1(&Day13, CRASH_EXAMPLE, "7,3");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_part2(&Day13, LAST_CART_EXAMPLE, "6,4");
+    }
+
+    #[test]
+    fn renders_the_map() {
+        let system: System = CRASH_EXAMPLE.parse().unwrap();
+        assert_eq!(system.to_string(), CRASH_EXAMPLE);
+    }
+
+    #[test]
+    fn records_every_tick() {
+        let system = Day13.parse(CRASH_EXAMPLE).unwrap();
+        let mut recorder = Recorder::new(100);
+        Day13.animate(Part::One, &system, &mut recorder).unwrap().unwrap();
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 15);
+        assert_eq!(frames[0], format!("Tick: 0\n{}", CRASH_EXAMPLE));
+        assert!(frames[14].starts_with("Tick: 14\n"));
+    }
+
+    #[test]
+    fn carts_must_stay_on_the_track() {
+        let err = Day13.parse("/->-\\\n|   |\n\\-<-/\n  v  ").err().unwrap();
+        assert!(matches!(err, Error::Parse(ParseError { line: Some(4), column: Some(3), .. })), "{:?}", err);
+        let err = Day13.parse("-->  <--").err().unwrap();
+        assert!(matches!(err, Error::Parse(ParseError { line: Some(1), column: Some(3), .. })), "{:?}", err);
+    }
+
+    #[test]
+    fn carts_on_separate_loops() {
+        let system = Day13.parse("/>\\ /<\\\n\\-/ \\-/").unwrap();
+        assert!(matches!(Day13.part1(&system), Err(Error::Unsolvable(_))));
+        assert!(matches!(Day13.part2(&system), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day13);
+    }
+}
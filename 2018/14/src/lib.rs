@@ -0,0 +1,307 @@
+use std::str::FromStr;
+
+use common::{Answer, Error, Params, Rng, Solution};
+
+/// The puzzle input the solution was written for, used when there is no input file
+const DEFAULT_INPUT: &str = "540561";
+
+/// Most recipes part 2 makes while searching, a few times more than real inputs need, since some
+/// scores never appear, such as any but 0 from a seed of `00`
+const MAX_RECIPES: usize = 100_000_000;
+
+pub struct Day14 {
+    /// Scores on the scoreboard before the elves start
+    seed: String,
+    /// Number of recipes to make, overriding the input
+    recipes: Option<String>,
+    /// Scores part 2 searches for, by default the digits of the input
+    needle: Option<String>
+}
+
+impl Default for Day14 {
+    fn default() -> Day14 {
+        Day14 {
+            seed: "37".to_string(),
+            recipes: None,
+            needle: None
+        }
+    }
+}
+
+impl Solution for Day14 {
+    type Model = Puzzle;
+
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        params.expect_only(&["seed", "recipes", "needle"])?;
+        if let Some(seed) = params.get("seed", "a list of scores")? {
+            self.seed = seed;
+        }
+        self.recipes = params.get("recipes", "a number of recipes")?;
+        self.needle = params.get("needle", "a list of scores")?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Puzzle, Error> {
+        let input = match (&self.recipes, input.trim()) {
+            (Some(recipes), _) => recipes.as_str(),
+            (None, "") => DEFAULT_INPUT,
+            (None, input) => input
+        };
+        let recipes = input.parse().map_err(|_| Error::parse(input, "a number of recipes"))?;
+
+        let needle = self.needle.as_deref().unwrap_or(input);
+        let needle: RecipeList = needle.parse()?;
+        if needle.len() == 0 {
+            return Err(Error::parse(input, "at least one score to search for"));
+        }
+
+        let seed: RecipeList = self.seed.parse()?;
+        if seed.len() < 2 {
+            return Err(Error::parse(&self.seed, "a seed of at least two scores"));
+        }
+
+        Ok(Puzzle {
+            seed,
+            recipes,
+            needle: needle.recipes
+        })
+    }
+
+    fn builtin_input(&self) -> bool {
+        true
+    }
+
+    fn part1(&self, puzzle: &Puzzle) -> Result<Answer, Error> {
+        let mut lab = Lab::new(puzzle.seed.clone());
+        Ok(lab.get_postfix_after(puzzle.recipes, 10).into())
+    }
+
+    fn part2(&self, puzzle: &Puzzle) -> Result<Answer, Error> {
+        let mut lab = Lab::new(puzzle.seed.clone());
+        let index = lab.get_match_index(&puzzle.needle, MAX_RECIPES).ok_or_else(|| {
+            Error::unsolvable(&format!("the scores do not appear within the first {} recipes", MAX_RECIPES))
+        })?;
+        Ok(index.into())
+    }
+
+    /// Generates a number of recipes with one digit for every tenfold of `size`, up to the six
+    /// digits of a real input, since part 2 searches for its digits.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut digits = 1;
+        let mut size = size;
+        while size >= 10 && digits < 6 {
+            size /= 10;
+            digits += 1;
+        }
+        let recipes = rng.range(10_i64.pow(digits - 1).max(1)..=10_i64.pow(digits) - 1);
+        Some(format!("{}\n", recipes))
+    }
+}
+
+pub struct Puzzle {
+    seed: RecipeList,
+    recipes: usize,
+    needle: Vec<Score>
+}
+
+type RecipeIndex = usize;
+
+#[derive(Debug)]
+struct Lab {
+    recipe_list: RecipeList,
+    elves: Vec<RecipeIndex>
+}
+
+impl Lab {
+    fn new(recipe_list: RecipeList) -> Lab {
+        Lab {
+            elves: vec![0, 1],
+            recipe_list
+        }
+    }
+
+    /// Number of recipes on the scoreboard before the needle first appears, or `None` if it does
+    /// not appear among the first `limit` recipes.
+    fn get_match_index(&mut self, needle: &[Score], limit: usize) -> Option<usize> {
+        let mut start = 0;
+        loop {
+            while start + needle.len() <= self.len() {
+                if self.recipe_list.get_list(start, needle.len()) == needle {
+                    return Some(start);
+                }
+                start += 1;
+            }
+            if self.len() >= limit {
+                return None;
+            }
+            let _ = self.generate_new();
+            self.move_elves();
+        }
+    }
+
+    fn move_elves(&mut self) {
+        self.elves[0] = self.recipe_list.get_new_index(self.elves[0]);
+        self.elves[1] = self.recipe_list.get_new_index(self.elves[1]);
+    }
+
+    fn generate_new(&mut self) -> &[u8] {
+        self.recipe_list.generate_new(self.elves[0], self.elves[1])
+    }
+
+    fn len(&self) -> usize {
+        self.recipe_list.len()
+    }
+
+    fn get_postfix_after(&mut self, offset: usize, count: usize) -> String {
+        while self.len() < offset + 10 {
+            let _ = self.generate_new();
+            self.move_elves();
+        }
+        self.recipe_list
+            .get_list(offset, count)
+            .iter()
+            .fold(String::new(), |mut buf, d| {
+                buf.push_str(&d.to_string());
+                buf
+            })
+    }
+}
+
+type Score = u8;
+
+#[derive(Debug, Clone)]
+pub struct RecipeList {
+    recipes: Vec<Score>
+}
+
+impl RecipeList {
+    fn get_list(&self, start: usize, count: usize) -> &[Score] {
+        let end = start + count;
+        &self.recipes[start..end]
+    }
+
+    fn len(&self) -> usize {
+        self.recipes.len()
+    }
+
+    fn get_new_index(&self, origin: RecipeIndex) -> RecipeIndex {
+        let offset = self.get(origin) as usize + 1usize;
+        (origin + offset) % self.recipes.len()
+    }
+
+    fn get(&self, index: RecipeIndex) -> Score {
+        self.recipes[index]
+    }
+
+    fn generate_new(&mut self, a: RecipeIndex, b: RecipeIndex) -> &[Score] {
+        let sum = self.recipes[a] + self.recipes[b];
+        let mut count = 0;
+        let n = sum % 10;
+        if sum >= 10 {
+            let m = (sum - n) / 10;
+            self.recipes.push(m);
+            count += 1;
+        }
+        self.recipes.push(n);
+        count += 1;
+
+        &self.recipes[(self.recipes.len() - count)..]
+    }
+}
+
+impl FromStr for RecipeList {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<RecipeList, Error> {
+        let scores = s.chars().enumerate().try_fold(vec![], |mut acc, (i, c)| {
+            match c.to_digit(10) {
+                Some(d) => {
+                    acc.push(d as Score);
+                    Ok(acc)
+                },
+                None => Err(Error::parse(&c.to_string(), "a digit").at_column(i + 1))
+            }
+        })?;
+        Ok(RecipeList {
+            recipes: scores
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use common::testing::{assert_generates, assert_matches_reference, assert_part1, assert_part2};
+
+    #[test]
+    fn part1_examples() {
+        assert_part1(&Day14::default(), "9", "5158916779");
+        assert_part1(&Day14::default(), "5", "0124515891");
+        assert_part1(&Day14::default(), "18", "9251071085");
+        assert_part1(&Day14::default(), "2018", "5941429882");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_part2(&Day14::default(), "51589", 9);
+        assert_part2(&Day14::default(), "01245", 5);
+        assert_part2(&Day14::default(), "92510", 18);
+        assert_part2(&Day14::default(), "59414", 2018);
+    }
+
+    #[test]
+    fn needles_are_digits() {
+        let day = Day14 { needle: Some("51x89".to_string()), ..Day14::default() };
+        assert!(day.parse("9").is_err());
+    }
+
+    #[test]
+    fn search_stops_at_the_limit() {
+        let mut lab = Lab::new("00".parse().unwrap());
+        assert_eq!(lab.get_match_index(&[1], 1000), None);
+        assert_eq!(lab.get_match_index(&[0, 0, 0], 1000), Some(0));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day14::default());
+    }
+
+    /// Makes recipes one at a time, checking the end of the scoreboard for the needle after
+    /// every single score.
+    fn reference(part: Part, puzzle: &Puzzle) -> Option<Answer> {
+        let mut scores = vec![];
+        let push = |scores: &mut Vec<Score>, score| {
+            scores.push(score);
+            part == Part::Two && scores.ends_with(&puzzle.needle)
+        };
+        for &score in &puzzle.seed.recipes {
+            if push(&mut scores, score) {
+                return Some((scores.len() - puzzle.needle.len()).into());
+            }
+        }
+        let mut elves = [0, 1];
+        loop {
+            if part == Part::One && scores.len() >= puzzle.recipes + 10 {
+                let digits: String = scores[puzzle.recipes..puzzle.recipes + 10].iter().map(|s| s.to_string()).collect();
+                return Some(digits.into());
+            }
+            let sum = scores[elves[0]] + scores[elves[1]];
+            let new = if sum >= 10 { vec![sum / 10, sum % 10] } else { vec![sum] };
+            for score in new {
+                if push(&mut scores, score) {
+                    return Some((scores.len() - puzzle.needle.len()).into());
+                }
+            }
+            for elf in &mut elves {
+                *elf = (*elf + 1 + scores[*elf] as usize) % scores.len();
+            }
+        }
+    }
+
+    #[test]
+    fn matches_reference() {
+        assert_matches_reference(&Day14::default(), 20, &[1, 10, 100], reference);
+    }
+}
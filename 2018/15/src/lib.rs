@@ -0,0 +1,564 @@
+use std::str::FromStr;
+use std::collections::{VecDeque, BTreeMap, BTreeSet};
+
+use log::{debug, trace};
+
+use common::{Answer, Error, Grid, Image, Part, Point2, ReadingOrder, Recorder, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Model = System;
+
+    fn parse(&self, input: &str) -> Result<System, Error> {
+        input.trim().parse()
+    }
+
+    fn part1(&self, system: &System) -> Result<Answer, Error> {
+        Ok(system.clone().run(&mut Recorder::off())?.into())
+    }
+
+    fn part2(&self, system: &System) -> Result<Answer, Error> {
+        let (_, checksum) = elves_winning(system)?;
+        Ok(checksum.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    /// Part 2 watches the battle at the lowest power the elves win with, not the lost ones
+    /// before it.
+    fn animate(&self, part: Part, system: &System, recorder: &mut Recorder) -> Option<Result<(), Error>> {
+        let mut system = system.clone();
+        if part == Part::Two {
+            match elves_winning(&system) {
+                Ok((power, _)) => system.set_elf_power(power),
+                Err(err) => return Some(Err(err))
+            }
+        }
+        Some(system.run(recorder).map(|_| ()))
+    }
+    /// The cave once the battle of part 1 is over, with elves in green and goblins in red.
+    fn image(&self, system: &System) -> Option<Result<Image, Error>> {
+        let mut system = system.clone();
+        Some(system.run(&mut Recorder::off()).map(|_| system.image()))
+    }
+}
+
+/// The lowest attack power that lets the elves win without losing a single elf, and the outcome
+/// of that battle.
+fn elves_winning(system: &System) -> Result<(i32, i32), Error> {
+    let mut power = 4;
+    loop {
+        let mut sys = system.clone();
+        sys.set_elf_power(power);
+        let pre_elves = sys.count_elves();
+        let checksum = sys.run(&mut Recorder::off())?;
+        if pre_elves == sys.count_elves() {
+            return Ok((power, checksum));
+        }
+        power += 1;
+    }
+}
+
+/// Generates a walled cave that grows with `size`, holding at least one goblin and one elf.
+/// Open squares the units cannot reach are walled up, since the battle would never end if the
+/// two sides could not get to each other.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let side = (size / 2 + 5).min(32);
+    loop {
+        let mut cave = Grid::from_fn(side, side, |(x, y)| {
+            let border = x == 0 || y == 0 || x == side - 1 || y == side - 1;
+            if border || rng.chance(1, 5) { '#' } else { '.' }
+        });
+        let open: Vec<(usize, usize)> = cave.iter().filter(|&(_, &c)| c == '.').map(|(p, _)| p).collect();
+        if open.is_empty() {
+            continue;
+        }
+
+        let mut reached = Grid::new(side, side, false);
+        let mut queue = VecDeque::new();
+        let start = *rng.choose(&open);
+        reached[start] = true;
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            for next in cave.neighbours4(position) {
+                if cave[next] == '.' && !reached[next] {
+                    reached[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut open: Vec<(usize, usize)> = open.into_iter().filter(|&p| reached[p]).collect();
+        if open.len() < 2 {
+            continue;
+        }
+        for position in cave.positions().collect::<Vec<_>>() {
+            if !reached[position] {
+                cave[position] = '#';
+            }
+        }
+
+        rng.shuffle(&mut open);
+        let units = (size / 5 + 2).min(open.len());
+        for (i, &position) in open.iter().take(units).enumerate() {
+            cave[position] = match i {
+                0 => 'G',
+                1 => 'E',
+                _ => *rng.choose(&['G', 'E'])
+            };
+        }
+        return cave.render(|_, &c| c);
+    }
+}
+
+#[derive(Clone)]
+pub struct System {
+    next_id: ActorId,
+    actors: Vec<Actor>,
+    map: Map,
+    tick: u32
+}
+
+impl System {
+    fn checksum(&self) -> i32 {
+        let hp: i32 = self.get_actors().iter().map(|(_, a)| {
+            self.actors[*a as usize].hp
+        }).sum();
+        hp * self.tick as i32
+    }
+
+    fn set_elf_power(&mut self, power: i32) {
+        for actor in &mut self.actors {
+            if actor.species == Species::Elf {
+                actor.attack_power = power;
+            }
+        }
+    }
+
+    /// Fights the battle to the end. A round in which no unit moves or attacks leaves everything
+    /// as it was, so the battle could never end, and is an error instead.
+    fn run(&mut self, recorder: &mut Recorder) -> Result<i32, Error> {
+        while self.count_goblins() > 0 && self.count_elves() > 0 {
+            trace!("Round {}: {} elves and {} goblins left", self.tick, self.count_elves(), self.count_goblins());
+            recorder.record(|| format!("Round: {}\n{}", self.tick, self));
+            if !self.tick() {
+                return Err(Error::unsolvable("the elves and goblins cannot reach each other"));
+            }
+        }
+        debug!("Battle over after round {} with {} elves and {} goblins left", self.tick, self.count_elves(), self.count_goblins());
+        recorder.record(|| format!("Round: {}\n{}", self.tick, self));
+        Ok(self.checksum())
+    }
+
+    /// Plays a round, returning whether any unit moved or attacked.
+    fn tick(&mut self) -> bool {
+        let mut acted = false;
+        let actors = self.get_actors();
+        for &(position, actor) in &actors {
+            if self.count_goblins() == 0 || self.count_elves() == 0 {
+                return true;
+            }
+            if self.actors[actor as usize].hp > 0 {
+                if let Some(step) = self.perform_move(actor, position) {
+                    acted |= step != position;
+                    acted |= self.perform_attack(actor, step);
+                } else {
+                    acted |= self.perform_attack(actor, position);
+                }
+            }
+        }
+        self.tick += 1;
+        acted
+    }
+
+    /// The map, with units fading the fewer hit points they have left.
+    fn image(&self) -> Image {
+        Image::from_grid(&self.map.grid, 8, |_, tile| {
+            match tile {
+                Tile::Wall => [60, 60, 60],
+                Tile::Empty => [200, 190, 170],
+                Tile::Actor(id) => {
+                    let actor = self.get_actor(id);
+                    let strength = (55 + actor.hp.clamp(0, 200)) as u8;
+                    match actor.species {
+                        Species::Elf => [0, strength, 0],
+                        Species::Goblin => [strength, 0, 0]
+                    }
+                }
+            }
+        })
+    }
+
+    fn count_goblins(&self) -> i32 {
+        self.actors.iter().filter(|a| a.hp > 0 && a.species == Species::Goblin).count() as i32
+    }
+
+    fn count_elves(&self) -> i32 {
+        self.actors.iter().filter(|a| a.hp > 0 && a.species == Species::Elf).count() as i32
+    }
+
+    fn get_actor(&self, id: &ActorId) -> &Actor {
+        &self.actors[*id as usize]
+    }
+
+    fn perform_move(&mut self, actor: ActorId, position: Position) -> Option<Position> {
+        let pos = self.get_walk_target(actor, position)?;
+        if pos == position {
+            Some(position)
+        } else {
+            let distances = self.map.distances(pos);
+            let pos = position.orthogonal_neighbours().iter()
+                .filter_map(|c| distances.get(c).map(|dist| (c, dist)))
+                .min_by_key(|&(_, dist)| dist)
+                .map(|(c, _)| *c);
+            if let Some(step) = pos {
+                self.map.set(&position, Tile::Empty);
+                self.map.set(&step, Tile::Actor(actor));
+                return Some(step);
+            }
+            Some(position)
+        }
+    }
+
+    fn get_walk_target(&self, actor: ActorId, position: Position) -> Option<Position> {
+        let actor = self.actors[actor as usize];
+        let closest = self.get_actors().iter().filter_map(|(coord, act)| {
+            if *act == actor.id || self.actors[*act as usize].species == actor.species {
+                None
+            } else {
+                Some(position.manhattan(coord))
+            }
+        }).min();
+        if closest == Some(1) {
+            return Some(position);
+        }
+        let distances = self.map.distances(position);
+        self.get_actors()
+            .iter()
+            .filter_map(|(coord, act)| {
+                if *act == actor.id || self.actors[*act as usize].species == actor.species {
+                    None
+                } else {
+                    Some(coord.orthogonal_neighbours()
+                        .iter()
+                        .filter_map(|c| {
+                            if self.map.get(c) != Some(Tile::Empty) && self.map.get(c) != Some(Tile::Actor(actor.id)) {
+                                None
+                            } else {
+                                Some(*c)
+                            }
+                        })
+                        .collect::<Vec<Position>>())
+                }
+            })
+            .flatten()
+            .filter_map(|pos| distances.get(&pos).map(|dist| (pos, dist)))
+            .min_by_key(|&(_, dist)| dist)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Attacks the weakest enemy next to `position`, returning whether there was one.
+    fn perform_attack(&mut self, actor: ActorId, position: Position) -> bool {
+        let actor = self.actors[actor as usize];
+        let target = position.orthogonal_neighbours().iter()
+            .filter_map(|pos| {
+                match self.map.get(pos) {
+                    Some(Tile::Actor(id)) => {
+                        let t = self.actors[id as usize];
+                        if t.species == actor.species {
+                            None
+                        } else {
+                            Some((t.hp, t.id, *pos))
+                        }
+                    },
+                    _ => None
+                }
+            })
+            .min_by_key(|t| t.0);
+
+        match target {
+            Some((_, target, pos)) => {
+                let hp = self.attack(&actor.id, &target);
+                if hp <= 0 {
+                    self.map.set(&pos, Tile::Empty);
+                }
+                true
+            },
+            None => false
+        }
+    }
+
+    fn attack(&mut self, attacker: &ActorId, target: &ActorId) -> i32 {
+        let power = self.actors[*attacker as usize].attack_power;
+        self.actors[*target as usize].hp -= power;
+        self.actors[*target as usize].hp
+    }
+
+    fn get_actors(&self) -> Vec<(Position, ActorId)> {
+        self.map.grid.iter().filter_map(|((x, y), tile)| {
+            match tile {
+                Tile::Actor(id) if self.actors[*id as usize].hp > 0 => Some(((x as i32, y as i32).into(), *id)),
+                _ => None
+            }
+        }).collect()
+    }
+
+    fn create_actor(&mut self, species: Species) -> ActorId {
+        self.actors.push(Actor {
+            id: self.next_id,
+            hp: 200,
+            attack_power: 3,
+            species
+        });
+        self.next_id += 1;
+        self.next_id - 1
+    }
+}
+
+impl FromStr for System {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<System, Error> {
+        let mut system = System {
+            tick: 0,
+            next_id: 0,
+            actors: vec![],
+            map: Map {
+                grid: Grid::new(0, 0, Tile::Empty)
+            }
+        };
+        let grid = Grid::parse(input, |_, c| {
+            match c {
+                '#' => Ok(Tile::Wall),
+                '.' => Ok(Tile::Empty),
+                'G' => Ok(Tile::Actor(system.create_actor(Species::Goblin))),
+                'E' => Ok(Tile::Actor(system.create_actor(Species::Elf))),
+                _ => Err(Error::parse(&c.to_string(), "a wall, an open square, a goblin or an elf"))
+            }
+        })?;
+        system.map = Map { grid };
+        Ok(system)
+    }
+}
+impl std::fmt::Display for System {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let buf = self.map.grid.render(|_, tile| {
+            match tile {
+                Tile::Wall => '#',
+                Tile::Empty => '.',
+                Tile::Actor(id) => {
+                    let actor = self.get_actor(id);
+                    match actor.species {
+                        Species::Elf => 'E',
+                        Species::Goblin => 'G',
+                    }
+                }
+            }
+        });
+        write!(f, "{}", buf)
+    }
+}
+
+
+type ActorId = u32;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+struct Actor {
+    id: ActorId,
+    hp: i32,
+    attack_power: i32,
+    species: Species
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Species {
+    Elf,
+    Goblin
+
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Tile {
+    Empty,
+    Wall,
+    Actor(ActorId),
+}
+
+#[derive(Clone)]
+struct Map {
+    grid: Grid<Tile>
+}
+
+impl Map {
+    fn set(&mut self, position: &Position, tile: Tile) {
+        self.grid[(position.x() as usize, position.y() as usize)] = tile
+    }
+
+    fn get(&self, position: &Position) -> Option<Tile> {
+        if position.x() < 0 || position.y() < 0 {
+            None
+        } else {
+            self.grid.get((position.x() as usize, position.y() as usize)).copied()
+        }
+    }
+
+    fn distances(&self, origin: Position) -> BTreeMap<Position, usize> {
+        let mut distances = BTreeMap::default();
+        distances.insert(origin, 0);
+
+        let mut todo = VecDeque::new();
+        todo.push_front(origin);
+        let mut visisted = BTreeSet::new();
+        let mut todo_set = BTreeSet::new();
+
+        while let Some(node) = todo.pop_front() {
+            visisted.insert(node);
+            todo_set.remove(&node);
+            let neighbours = node.orthogonal_neighbours();
+            for neighbour in &neighbours {
+                if visisted.contains(neighbour) {
+                    continue;
+                }
+                if self.get(neighbour) != Some(Tile::Empty) {
+                    visisted.insert(*neighbour);
+                    continue;
+                }
+                if !todo_set.contains(neighbour) {
+                    todo.push_back(*neighbour);
+                    todo_set.insert(*neighbour);
+                }
+
+                let dist = 1 + *distances.get(&node).unwrap_or(&0);
+                if !distances.contains_key(neighbour) || dist < distances[neighbour] {
+                    distances.insert(*neighbour, dist);
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+/// Units take their turns and pick their targets in reading order
+type Position = Point2<i32, ReadingOrder>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_example, assert_generates, assert_part1};
+
+    #[test]
+    fn first_example() {
+        let battle = "\
+#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######";
+        assert_example(&Day15, battle, 27730, 4988);
+    }
+
+    #[test]
+    fn second_example() {
+        let battle = "\
+#######
+#G..#E#
+#E#E.E#
+#G.##.#
+#...#E#
+#...E.#
+#######";
+        assert_part1(&Day15, battle, 36334);
+    }
+
+    #[test]
+    fn third_example() {
+        let battle = "\
+#######
+#E..EG#
+#.#G.E#
+#E.##E#
+#G..#.#
+#..E#.#
+#######";
+        assert_example(&Day15, battle, 39514, 31284);
+    }
+
+    #[test]
+    fn fourth_example() {
+        let battle = "\
+#######
+#E.G#.#
+#.#G..#
+#G.#.G#
+#G..#.#
+#...E.#
+#######";
+        assert_example(&Day15, battle, 27755, 3478);
+    }
+
+    #[test]
+    fn fifth_example() {
+        let battle = "\
+#######
+#.E...#
+#.#..G#
+#.###.#
+#E#G#G#
+#...#G#
+#######";
+        assert_example(&Day15, battle, 28944, 6474);
+    }
+
+    #[test]
+    fn sixth_example() {
+        let battle = "\
+#########
+#G......#
+#.E.#...#
+#..##..G#
+#...##..#
+#...#...#
+#.G...G.#
+#.....G.#
+#########";
+        assert_example(&Day15, battle, 18740, 1140);
+    }
+
+    #[test]
+    fn records_every_round() {
+        let system = Day15.parse("#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######").unwrap();
+        let mut recorder = Recorder::new(100);
+        Day15.animate(Part::One, &system, &mut recorder).unwrap().unwrap();
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 48);
+        assert_eq!(frames[47].trim_end(), "Round: 47\n#######\n#G....#\n#.G...#\n#.#.#G#\n#...#.#\n#....G#\n#######");
+    }
+
+    #[test]
+    fn image_of_the_battle() {
+        let system = Day15.parse("#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######").unwrap();
+        let image = Day15.image(&system).unwrap().unwrap();
+        assert_eq!((image.width(), image.height()), (56, 56));
+        assert_eq!(image.pixel((0, 0)), [60, 60, 60]);
+        // The goblin left at 1,1 has all of its 200 hit points
+        assert_eq!(image.pixel((8, 8)), [255, 0, 0]);
+    }
+
+    #[test]
+    fn sides_that_cannot_reach_each_other() {
+        let system = Day15.parse("#####\n#G#E#\n#####").unwrap();
+        assert!(matches!(Day15.part1(&system), Err(Error::Unsolvable(_))));
+        assert!(matches!(Day15.part2(&system), Err(Error::Unsolvable(_))));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day15);
+    }
+}
@@ -0,0 +1,383 @@
+#[macro_use]
+extern crate strum_macros;
+
+use log::{debug, trace};
+use strum::IntoEnumIterator;
+
+use std::str::FromStr;
+
+use common::{Answer, Error, Parser, Rng, Solution};
+
+const REGISTER_FORMAT: &str = "a register like '[3, 2, 1, 1]'";
+
+#[derive(Default)]
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Model = (Vec<Test>, Vec<[u32; 4]>);
+
+    fn parse(&self, input: &str) -> Result<(Vec<Test>, Vec<[u32; 4]>), Error> {
+        parse_input(input.trim())
+    }
+
+    fn part1(&self, (tests, _): &(Vec<Test>, Vec<[u32; 4]>)) -> Result<Answer, Error> {
+        let system = System::new();
+        Ok(tests.iter().filter(|test| system.count_matching(test) >= 3).count().into())
+    }
+
+    fn part2(&self, (tests, program): &(Vec<Test>, Vec<[u32; 4]>)) -> Result<Answer, Error> {
+        let mut system = System::new();
+
+        while system.codes.iter().any(|c| c.is_none()) {
+            let known = system.codes.iter().filter(|c| c.is_some()).count();
+            tests.iter().for_each(|test| {
+                system.find_valid(test);
+            });
+            if known == system.codes.iter().filter(|c| c.is_some()).count() {
+                return Err(Error::unsolvable("could not determine all op codes"));
+            }
+        }
+
+        let mut register = Register::new(0, 0, 0, 0);
+        for line in program {
+            register = system.execute(&register, line[0], line[1], line[2], line[3]);
+        }
+
+        Ok(register.get(0).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
+/// Generates `size` samples followed by a program of `size` instructions, with the op codes
+/// numbered in a random order. Register values in the samples stay below 4 so they remain single
+/// digits after any instruction, and the program never lets a register grow large enough for an
+/// instruction to overflow. Random samples seldom tell all op codes apart, so a sample that only
+/// one of the op codes not yet worked out matches is mixed in for each of them.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut op_codes: Vec<OpCode> = OpCode::iter().collect();
+    rng.shuffle(&mut op_codes);
+    let system = System::new();
+    let random_instruction = |rng: &mut Rng| {
+        let number = rng.index(op_codes.len());
+        let [a, b, c] = [0; 3].map(|_| rng.below(4) as u32);
+        (op_codes[number], [number as u32, a, b, c])
+    };
+    let random_test = |rng: &mut Rng| {
+        let before = Register { inner: [0; 4].map(|_| rng.below(4) as u32) };
+        let (op_code, input) = random_instruction(rng);
+        let after = system.run(op_code, &before, input[1], input[2], input[3]);
+        Test { before, input, after }
+    };
+
+    let mut tests: Vec<Test> = (0..size.max(1)).map(|_| random_test(rng)).collect();
+    let mut known = System::new();
+    while known.codes.iter().any(|code| code.is_none()) {
+        let test = random_test(rng);
+        if known.codes[test.input[0] as usize].is_none() && known.find_valid(&test).len() == 1 {
+            tests.push(test);
+        }
+    }
+    rng.shuffle(&mut tests);
+
+    let mut buf = String::new();
+    for test in &tests {
+        let [number, a, b, c] = test.input;
+        buf += &format!("Before: {:?}\n{} {} {} {}\nAfter:  {:?}\n\n", test.before.inner, number, a, b, c, test.after.inner);
+    }
+
+    buf += "\n\n";
+    let mut register = Register::new(0, 0, 0, 0);
+    for _ in 0..size.max(1) {
+        let [number, a, b, c] = loop {
+            let (op_code, instruction) = random_instruction(rng);
+            let next = system.run(op_code, &register, instruction[1], instruction[2], instruction[3]);
+            if next.inner.iter().all(|&value| value < 1 << 15) {
+                register = next;
+                break instruction;
+            }
+        };
+        buf += &format!("{} {} {} {}\n", number, a, b, c);
+    }
+    buf
+}
+
+fn parse_input(input: &str) -> Result<(Vec<Test>, Vec<[u32; 4]>), Error> {
+    let mut before: Option<Register> = None;
+    let mut inp: Option<[u32; 4]> = None;
+
+    let mut tests = vec![];
+
+    let mut reading_test = false;
+
+    let mut program: Vec<[u32; 4]> = vec![];
+
+    for (i, line) in input.lines().enumerate() {
+        if line.starts_with("Before:") {
+            before = Some(parse_register(line, "Before:").map_err(|err| err.at_line(i + 1))?);
+            reading_test = true;
+        } else if line.starts_with("After:") {
+            let after = parse_register(line, "After:").map_err(|err| err.at_line(i + 1))?;
+
+            let (before, input) = match (before.take(), inp.take()) {
+                (Some(before), Some(input)) => (before, input),
+                _ => {
+                    let expected = "a 'Before:' line and an instruction ahead of the 'After:' line";
+                    return Err(Error::parse(line, expected).at_line(i + 1));
+                }
+            };
+            tests.push(Test {
+                before,
+                input,
+                after,
+            });
+            reading_test = false;
+        } else if !line.trim().is_empty() {
+            let instruction = parse_instruction(line).map_err(|err| err.at_line(i + 1))?;
+            if reading_test {
+                inp = Some(instruction);
+            } else {
+                program.push(instruction);
+            }
+        }
+    }
+    Ok((tests, program))
+}
+
+fn parse_instruction(line: &str) -> Result<[u32; 4], Error> {
+    const FORMAT: &str = "an instruction like '9 2 1 2', with an op code below 16 and operands below 4";
+    let mut parser = Parser::new(line, FORMAT);
+    let instruction = parser.ints(" ")?;
+    parser.end()?;
+    // Operands may name registers, and there are only four of them
+    if instruction[0] >= 16 || instruction[1..].iter().any(|&operand| operand >= 4) {
+        return Err(Error::parse(line, FORMAT));
+    }
+    Ok(instruction)
+}
+
+pub struct Test {
+    before: Register,
+    input: [u32; 4],
+    after: Register
+}
+
+struct System { 
+    codes: [Option<OpCode>; 16]
+}
+
+impl System {
+    fn new() -> System {
+        System {
+            codes: [None; 16]
+        }
+    }
+
+    fn execute(&self, input: &Register, i: u32, a: u32, b: u32, c: u32) -> Register {
+        self.run(self.codes[i as usize].unwrap(), input, a, b, c)
+    }
+
+    fn count_matching(&self, test: &Test) -> usize {
+        OpCode::iter()
+            .filter(|&op_code| self.run(op_code, &test.before, test.input[1], test.input[2], test.input[3]) == test.after)
+            .count()
+    }
+
+    /// The op codes not yet worked out that the test matches. When it is only one, that is the
+    /// op code of the test's number, unless the number is already known.
+    fn find_valid(&mut self, test: &Test) -> Vec<OpCode> {
+        let mut out = vec![];
+        for op_code in OpCode::iter() {
+            if !self.codes.contains(&Some(op_code))
+                && self.run(op_code, &test.before, test.input[1], test.input[2], test.input[3]) == test.after {
+                out.push(op_code);
+            }
+        }
+
+        let code = test.input[0] as usize;
+        if out.len() == 1 && self.codes[code].is_none() {
+            debug!("Op code {} is {:?}", code, out[0]);
+            self.codes[code] = Some(out[0]);
+        }
+        out
+    }
+
+    fn run(&self, opcode: OpCode, register: &Register, a: u32, b: u32, c: u32) -> Register {
+        trace!("{:?} {} {} {} on {:?}", opcode, a, b, c, register);
+        match opcode {
+            // Registers hold any u32, so sums and products wrap around rather than overflow
+            OpCode::Addr => register.set(c, register.get(a).wrapping_add(register.get(b))),
+            OpCode::Addi => register.set(c, register.get(a).wrapping_add(b)),
+            OpCode::Muli => register.set(c, register.get(a).wrapping_mul(b)),
+            OpCode::Mulr => register.set(c, register.get(a).wrapping_mul(register.get(b))),
+            OpCode::Seti => register.set(c, a),
+            OpCode::Setr => register.set(c, register.get(a)),
+            OpCode::Bani => register.set(c, register.get(a) & b),
+            OpCode::Banr => register.set(c, register.get(a) & register.get(b)),
+            OpCode::Bori => register.set(c, register.get(a) | b),
+            OpCode::Borr => register.set(c, register.get(a) | register.get(b)),
+            OpCode::Eqir => {
+                if a == register.get(b) {
+                    register.set(c, 1)
+                } else {
+                    register.set(c, 0)
+                }
+            },
+            OpCode::Eqri => {
+                if register.get(a) == b {
+                    register.set(c, 1)
+                } else {
+                    register.set(c, 0)
+                }
+            },
+            OpCode::Eqrr => {
+                if register.get(a) == register.get(b) {
+                    register.set(c, 1)
+                } else {
+                    register.set(c, 0)
+                }
+            },
+            OpCode::Gtir => {
+                if a > register.get(b) {
+                    register.set(c, 1)
+                } else {
+                    register.set(c, 0)
+                }
+            },
+            OpCode::Gtri => {
+                if register.get(a) > b {
+                    register.set(c, 1)
+                } else {
+                    register.set(c, 0)
+                }
+            },
+            OpCode::Gtrr => {
+                if register.get(a) > register.get(b) {
+                    register.set(c, 1)
+                } else {
+                    register.set(c, 0)
+                }
+            }
+        }
+    }
+}
+
+#[derive(EnumIter, Debug, Copy, Clone, Eq, PartialEq)]
+enum OpCode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Seti,
+    Setr,
+    Bani,
+    Banr,
+    Bori,
+    Borr,
+    Eqir,
+    Eqri,
+    Eqrr,
+    Gtir,
+    Gtri,
+    Gtrr,
+
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Register {
+    inner: [u32; 4]
+}
+
+impl Register {
+    fn new(a: u32, b: u32, c: u32, d: u32) -> Register {
+        Register {
+            inner: [a, b, c, d]
+        }
+    }
+
+    fn get(&self, i: u32) -> u32 {
+        self.inner[i as usize]
+    }
+
+    fn set(mut self, i: u32, v: u32) -> Self {
+        self.inner[i as usize] = v;
+        self
+    }
+}
+
+impl FromStr for Register {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Register, Error> {
+        parse_register(s, "")
+    }
+}
+
+/// Parses a register after a label such as `Before:`, keeping the columns of any error right.
+fn parse_register(line: &str, label: &str) -> Result<Register, Error> {
+    let mut parser = Parser::new(line, REGISTER_FORMAT);
+    parser.token(label)?;
+    parser.spaces();
+    let inner = parser.vector()?;
+    parser.end()?;
+    Ok(Register { inner })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_generates, assert_part1, assert_solves_generated};
+
+    const EXAMPLE: &str = "\
+Before: [3, 2, 1, 1]
+9 2 1 2
+After:  [3, 2, 2, 1]
+";
+
+    #[test]
+    fn part1_example() {
+        assert_part1(&Day16, EXAMPLE, 1);
+    }
+
+    #[test]
+    fn sample_matches_three_opcodes() {
+        let (tests, _) = Day16.parse(EXAMPLE).unwrap();
+        assert_eq!(System::new().count_matching(&tests[0]), 3);
+    }
+
+    #[test]
+    fn after_without_before_is_an_error() {
+        assert!(Day16.parse("After:  [3, 2, 2, 1]").is_err());
+        assert!(Day16.parse("Before: [3, 2, 1, 1]\nAfter:  [3, 2, 2, 1]").is_err());
+    }
+
+    #[test]
+    fn instructions_must_fit_the_registers() {
+        assert!(Day16.parse("Before: [3, 2, 1, 1]\n9 2 1 4\nAfter:  [3, 2, 2, 1]").is_err());
+        assert!(Day16.parse("Before: [3, 2, 1, 1]\n16 2 1 2\nAfter:  [3, 2, 2, 1]").is_err());
+    }
+
+    #[test]
+    fn registers_hold_numbers_of_any_size() {
+        let (tests, _) = Day16.parse("Before: [3, 20, 1, 1]\n9 2 1 2\nAfter:  [3, 20, 2, 1]").unwrap();
+        assert_eq!(tests[0].before.inner, [3, 20, 1, 1]);
+    }
+
+    #[test]
+    fn arithmetic_wraps_around() {
+        let (tests, _) = Day16.parse("Before: [4294967295, 4294967295, 0, 0]\n0 0 1 2\nAfter:  [4294967295, 4294967295, 4294967294, 0]").unwrap();
+        assert_eq!(System::new().count_matching(&tests[0]), 1);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        assert_generates(&Day16);
+    }
+
+    #[test]
+    fn generated_inputs_tell_all_op_codes_apart() {
+        assert_solves_generated(&Day16, &[1, 2, 5, 20]);
+    }
+}
@@ -0,0 +1,131 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use clap::Args;
+use common::{Error, Params, Part, Recorder};
+use serde::Serialize;
+
+use crate::{input, parse_param, registry};
+
+#[derive(Args)]
+pub struct AnimateArgs {
+    year: u32,
+
+    day: u32,
+
+    /// The part whose simulation is shown
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: u32,
+
+    /// Frames shown per second
+    #[arg(long, default_value_t = 10.0, value_parser = parse_fps)]
+    fps: f64,
+
+    /// Most frames to record, counted from the start of the simulation
+    #[arg(long, default_value_t = 1000)]
+    frames: usize,
+
+    /// Writes an asciinema recording to this file instead of playing the frames
+    #[arg(long)]
+    cast: Option<PathBuf>,
+
+    /// Reads the puzzle input from this file instead of the day's input directory, or from
+    /// standard input for `-`. An input piped in is read without it
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Sets a puzzle value that is not part of the input, as for `run`
+    #[arg(short, long = "param", value_name = "KEY=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>
+}
+
+fn parse_fps(fps: &str) -> Result<f64, String> {
+    match fps.parse::<f64>() {
+        Ok(fps) if fps > 0.0 && fps.is_finite() => Ok(fps),
+        _ => Err(format!("expected a positive number of frames per second, found '{}'", fps))
+    }
+}
+
+/// The header line of an asciinema v2 recording.
+#[derive(Serialize)]
+struct CastHeader {
+    version: u32,
+    width: usize,
+    height: usize,
+    title: String
+}
+
+/// Records the simulation behind a part of a day, and plays it in the terminal or saves it.
+pub fn animate(root: &Path, args: &AnimateArgs) -> Result<(), Error> {
+    let day = registry::find(args.year, args.day)
+        .ok_or_else(|| format!("No solution for {} day {}", args.year, args.day))?;
+    let part = Part::ALL[args.part as usize - 1];
+
+    let input = match &args.input {
+        Some(path) => input::read(path)?,
+        None if input::piped() => input::read(Path::new("-"))?,
+        None => input::read_cached(root, day)?
+    };
+
+    let mut solver = (day.solver)();
+    solver.configure(&args.params.iter().cloned().collect::<Params>())?;
+    let model = solver.parse_input(&input)?;
+    let mut recorder = Recorder::new(args.frames);
+    solver.animate(part, &model, &mut recorder)
+        .ok_or_else(|| format!("{} day {} has no simulation to animate", day.year, day.day))??;
+
+    let title = format!("{} day {:02} part {}", day.year, day.day, part);
+    match &args.cast {
+        Some(path) => write_cast(path, &recorder, args.fps, title),
+        None => play(&recorder, args.fps)
+    }
+}
+
+/// Draws a frame over the previous one. Each line clears the rest of its row and the screen is
+/// cleared below the frame, so nothing of a larger frame lingers. There is no newline after the
+/// last line, which would scroll a screen that is just tall enough.
+fn screen(frame: &str) -> String {
+    let lines: Vec<&str> = frame.lines().collect();
+    format!("\x1b[H{}\x1b[K\x1b[J", lines.join("\x1b[K\r\n"))
+}
+
+fn play(recorder: &Recorder, fps: f64) -> Result<(), Error> {
+    let delay = Duration::from_secs_f64(1.0 / fps);
+    let mut out = io::stdout().lock();
+    write!(out, "\x1b[2J")?;
+    for frame in recorder.frames() {
+        write!(out, "{}", screen(frame))?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+fn write_cast(path: &Path, recorder: &Recorder, fps: f64, title: String) -> Result<(), Error> {
+    let (width, height) = recorder.size();
+    let header = CastHeader {
+        version: 2,
+        width: width.max(1),
+        height: height.max(1),
+        title
+    };
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "{}", to_json(&header)?)?;
+    for (i, frame) in recorder.frames().iter().enumerate() {
+        let mut output = screen(frame);
+        if i == 0 {
+            output.insert_str(0, "\x1b[2J");
+        }
+        writeln!(writer, "{}", to_json(&(i as f64 / fps, "o", output))?)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, Error> {
+    serde_json::to_string(value).map_err(|err| Error::Other(err.to_string()))
+}
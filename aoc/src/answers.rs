@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use common::{Error, Part};
+
+/// The known answers for one year, stored in `<root>/<year>/answers.json` as day -> part -> answer.
+pub struct Answers {
+    path: PathBuf,
+    days: BTreeMap<u32, BTreeMap<u32, String>>
+}
+
+impl Answers {
+    pub fn load(root: &Path, year: u32) -> Result<Answers, Error> {
+        let path = root.join(year.to_string()).join("answers.json");
+        let days = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|err| format!("Could not read {}: {}", path.display(), err))?,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into())
+        };
+        Ok(Answers { path, days })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.days.get(&day)?.get(&part.number()).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u32, part: Part, answer: String) {
+        self.days.entry(day).or_default().insert(part.number(), answer);
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let mut json = serde_json::to_string_pretty(&self.days).map_err(|err| Error::Other(err.to_string()))?;
+        json.push('\n');
+        fs::write(&self.path, json)?;
+        Ok(())
+    }
+}
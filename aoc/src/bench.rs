@@ -0,0 +1,141 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use common::{Error, Part, Solver};
+use serde::Serialize;
+
+use crate::registry::Day;
+use crate::timing::{format_duration, Stats};
+use crate::{input, select_days, Selection};
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Number of times each stage is run
+    #[arg(long, short = 'n', default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Writes the timings to this file, as CSV if it ends in `.csv` and as JSON otherwise
+    #[arg(long)]
+    report: Option<PathBuf>
+}
+
+/// One row of the benchmark report. Timings are in nanoseconds.
+#[derive(Serialize)]
+struct Record {
+    year: u32,
+    day: u32,
+    stage: String,
+    iterations: u32,
+    min_ns: u64,
+    median_ns: u64,
+    max_ns: u64
+}
+
+impl Record {
+    fn new(day: &Day, stage: &str, iterations: u32, stats: &Stats) -> Record {
+        Record {
+            year: day.year,
+            day: day.day,
+            stage: stage.to_string(),
+            iterations,
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            max_ns: stats.max.as_nanos() as u64
+        }
+    }
+}
+
+pub fn bench(root: &Path, args: &BenchArgs) -> Result<(), Error> {
+    let days = select_days(&args.selection)?;
+
+    let mut records = vec![];
+    let mut failed = 0;
+    for day in days {
+        println!("{} day {:02}", day.year, day.day);
+        let result = input::read_cached(root, day)
+            .map_err(Error::from)
+            .and_then(|input| bench_day((day.solver)().as_ref(), &input, args.iterations));
+        match result {
+            Ok(stages) => {
+                for (stage, stats) in &stages {
+                    println!(
+                        "  {:<6} min {:>9}  median {:>9}  max {:>9}",
+                        stage,
+                        format_duration(stats.min),
+                        format_duration(stats.median),
+                        format_duration(stats.max)
+                    );
+                    records.push(Record::new(day, stage, args.iterations, stats));
+                }
+            },
+            Err(err) => {
+                println!("  Failed: {}", err);
+                failed += 1;
+            }
+        }
+    }
+
+    if let Some(ref path) = args.report {
+        write_report(path, &records)?;
+    }
+
+    if failed > 0 {
+        return Err(Error::from(format!("{} day(s) failed", failed)));
+    }
+    Ok(())
+}
+
+/// Runs every stage of a solution the given number of times. Each iteration parses the input
+/// anew, and solves both parts from that model.
+fn bench_day(solver: &dyn Solver, input: &str, iterations: u32) -> Result<Vec<(String, Stats)>, Error> {
+    let mut parse = vec![];
+    let mut parts: Vec<Vec<Duration>> = vec![vec![]; Part::ALL.len()];
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let model = solver.parse_input(input)?;
+        parse.push(start.elapsed());
+
+        for (i, &part) in Part::ALL.iter().enumerate() {
+            let start = Instant::now();
+            solver.solve(part, &model)?;
+            parts[i].push(start.elapsed());
+        }
+    }
+
+    let mut stages = vec![("parse".to_string(), Stats::from_samples(&mut parse))];
+    for (&part, samples) in Part::ALL.iter().zip(parts.iter_mut()) {
+        stages.push((format!("part{}", part), Stats::from_samples(samples)));
+    }
+    Ok(stages)
+}
+
+fn write_report(path: &Path, records: &[Record]) -> Result<(), Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    if path.extension().is_some_and(|ext| ext == "csv") {
+        write_csv(&mut writer, records)?;
+    } else {
+        serde_json::to_writer_pretty(&mut writer, records).map_err(|err| Error::Other(err.to_string()))?;
+        writeln!(writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_csv<W: Write>(writer: &mut W, records: &[Record]) -> io::Result<()> {
+    writeln!(writer, "year,day,stage,iterations,min_ns,median_ns,max_ns")?;
+    for r in records {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            r.year, r.day, r.stage, r.iterations, r.min_ns, r.median_ns, r.max_ns
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Args;
+use common::Error;
+
+use crate::input;
+
+/// Sent with every request, so the Advent of Code admins know where the traffic comes from.
+const USER_AGENT: &str = "github.com/mipli/advent-of-code";
+
+#[derive(Args)]
+pub struct FetchArgs {
+    #[arg(value_parser = clap::value_parser!(u32).range(2015..))]
+    year: u32,
+
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+
+    /// Session cookie of a logged in Advent of Code user
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// Server to download the inputs from
+    #[arg(long, env = "AOC_BASE_URL", default_value = "https://adventofcode.com")]
+    base_url: String
+}
+
+/// Downloads the input of a day into its input directory. Inputs never change once published, so
+/// a day that already has an input file is never requested again.
+pub fn fetch(root: &Path, args: &FetchArgs) -> Result<(), Error> {
+    let path = input::path(root, args.year, args.day);
+    if path.exists() {
+        println!("{} day {:02}: cached in {}", args.year, args.day, path.display());
+        return Ok(());
+    }
+
+    let session = args.session.as_ref()
+        .ok_or("A session token is needed to download inputs, set AOC_SESSION or pass --session")?;
+    let url = format!("{}/{}/day/{}/input", args.base_url.trim_end_matches('/'), args.year, args.day);
+    let input = download(&url, session)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Written next to the real file first, so an interrupted download is never taken for a
+    // cached input.
+    let partial = path.with_extension("partial");
+    fs::write(&partial, input)?;
+    fs::rename(&partial, &path)?;
+
+    println!("{} day {:02}: downloaded to {}", args.year, args.day, path.display());
+    Ok(())
+}
+
+fn download(url: &str, session: &str) -> Result<String, Error> {
+    let response = ureq::get(url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|err| match err {
+            ureq::Error::Status(status, _) => format!("{} answered with status {}", url, status),
+            err => format!("Could not download {}: {}", url, err)
+        })?;
+    Ok(response.into_string()?)
+}
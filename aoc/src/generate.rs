@@ -0,0 +1,32 @@
+use std::io::{self, Write};
+
+use clap::Args;
+use common::{Error, Rng};
+
+use crate::registry;
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    year: u32,
+
+    day: u32,
+
+    /// Seed for the random generator, the same seed always gives the same input
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Roughly the number of lines or items in the input
+    #[arg(long, default_value_t = 20)]
+    size: usize
+}
+
+/// Writes a random input for a day to standard output, in the day's input format.
+pub fn generate(args: &GenerateArgs) -> Result<(), Error> {
+    let day = registry::find(args.year, args.day)
+        .ok_or_else(|| format!("No solution for {} day {}", args.year, args.day))?;
+    let solver = (day.solver)();
+    let input = solver.generate(&mut Rng::new(args.seed), args.size)
+        .ok_or_else(|| format!("{} day {} has no input generator", args.year, args.day))?;
+    io::stdout().write_all(input.as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use crate::registry::Day;
+
+/// Location of the puzzle input for a day, `<root>/<year>/<day>/input/input`.
+pub fn path(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(year.to_string())
+        .join(format!("{:02}", day))
+        .join("input")
+        .join("input")
+}
+
+/// Reads a puzzle input from a file, or from standard input for a path of `-`.
+pub fn read(path: &Path) -> io::Result<String> {
+    if is_stdin(path) {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    }
+    fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("could not read {}: {}", path.display(), err)))
+}
+
+/// Reads the cached puzzle input of a day. Days without one only get an empty input if their
+/// values are built into the solution.
+pub fn read_cached(root: &Path, day: &Day) -> io::Result<String> {
+    let path = path(root, day.year, day.day);
+    match fs::read_to_string(&path) {
+        Ok(input) => Ok(input),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+            if (day.solver)().builtin_input() {
+                Ok(String::new())
+            } else {
+                let message = format!("no input in {}, aoc fetch {} {} downloads it", path.display(), day.year, day.day);
+                Err(io::Error::new(io::ErrorKind::NotFound, message))
+            }
+        },
+        Err(err) => Err(err)
+    }
+}
+
+/// Whether `path` is `-`, which stands for standard input.
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Whether an input is piped or redirected into standard input. A terminal is not, and neither
+/// is a device such as `/dev/null`, which is what scripts and schedulers often leave it as.
+pub fn piped() -> bool {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::fd::AsFd;
+        use std::os::unix::fs::FileTypeExt;
+
+        let file = match stdin.as_fd().try_clone_to_owned() {
+            Ok(fd) => fs::File::from(fd),
+            Err(_) => return false
+        };
+        file.metadata().is_ok_and(|metadata| metadata.file_type().is_fifo() || metadata.is_file())
+    }
+    #[cfg(not(unix))]
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::registry;
+
+    #[test]
+    fn only_built_in_values_stand_in_for_a_missing_input() {
+        let root = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let missing = read_cached(&root, registry::find(2018, 1).unwrap());
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(read_cached(&root, registry::find(2018, 11).unwrap()).unwrap(), "");
+        assert!(read(&root.join("typo.txt")).is_err());
+    }
+}
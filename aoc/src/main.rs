@@ -0,0 +1,319 @@
+mod animate;
+mod answers;
+mod bench;
+mod fetch;
+mod generate;
+mod input;
+mod memory;
+mod output;
+mod pool;
+mod registry;
+mod scaffold;
+mod summary;
+mod timing;
+mod verify;
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::{ArgAction, Args, Parser, Subcommand};
+use log::LevelFilter;
+use common::{Answer, Error, Params, Part};
+
+use registry::Day;
+
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code solutions")]
+struct Cli {
+    /// Directory containing the `<year>/<day>` solution directories
+    #[arg(long, global = true, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+    root: PathBuf,
+
+    /// Logs what the solutions are doing to standard error, -vv for every step
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Logs nothing but errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log levels for single modules, like `aoc_2018_07=trace` or `aoc_2018_15=debug,aoc=warn`
+    #[arg(long, global = true, env = "RUST_LOG", value_name = "FILTER")]
+    log: Option<String>,
+
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves both parts of a day, or of every day in a year
+    Run(RunArgs),
+    /// Times parsing and both parts of a day, or of every day in a year, over many runs
+    Bench(bench::BenchArgs),
+    /// Checks the answers of a day, or of every day in a year, against the known answers
+    Verify(verify::VerifyArgs),
+    /// Downloads the puzzle input of a day, unless it is already cached
+    Fetch(fetch::FetchArgs),
+    /// Creates the crate for a new day and registers it with the runner
+    New(scaffold::NewArgs),
+    /// Writes a random puzzle input for a day, for stress and property testing
+    Generate(generate::GenerateArgs),
+    /// Plays the simulation behind a part of a day in the terminal, or saves it as an asciinema
+    /// recording
+    Animate(animate::AnimateArgs)
+}
+
+#[derive(Args)]
+struct Selection {
+    year: u32,
+
+    #[arg(required_unless_present = "all")]
+    day: Option<u32>,
+
+    /// Selects every day of the year
+    #[arg(long, conflicts_with = "day")]
+    all: bool
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Reads the puzzle input from this file instead of the day's input directory, or from
+    /// standard input for `-`. An input piped in is read without it
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Sets a puzzle value that is not part of the input, such as `workers=2` for 2018 day 7
+    #[arg(short, long = "param", value_name = "KEY=VALUE", value_parser = parse_param, conflicts_with = "all")]
+    params: Vec<(String, String)>,
+
+    /// How to print the answers
+    #[arg(long, value_enum, default_value_t)]
+    format: output::Format,
+
+    /// Draws a picture of the puzzle to this file, as PPM if it ends in `.ppm` and as PNG
+    /// otherwise
+    #[arg(long, conflicts_with = "all")]
+    image: Option<PathBuf>,
+
+    /// Writes the data behind the answers to this CSV file
+    #[arg(long, conflicts_with = "all")]
+    export: Option<PathBuf>,
+
+    /// Number of days solved at the same time, by default one for each core
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+
+    /// Time a day may take before the summary flags it, such as `500ms` or `2s`
+    #[arg(long, default_value = "1s", value_parser = timing::parse_duration)]
+    budget: Duration
+}
+
+fn parse_param(param: &str) -> Result<(String, String), String> {
+    match param.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, found '{}'", param))
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_logging(&cli);
+
+    let result = match cli.command {
+        Command::Run(args) => run(&cli.root, &args),
+        Command::Bench(args) => bench::bench(&cli.root, &args),
+        Command::Verify(args) => verify::verify(&cli.root, &args),
+        Command::Fetch(args) => fetch::fetch(&cli.root, &args),
+        Command::New(args) => scaffold::new(&cli.root, &args),
+        Command::Generate(args) => generate::generate(&args),
+        Command::Animate(args) => animate::animate(&cli.root, &args)
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+/// Sends log records to standard error, at the level picked by `-v` and `-q`, with the filters
+/// from `--log` on top.
+fn init_logging(cli: &Cli) {
+    let level = match (cli.quiet, cli.verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace
+    };
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level).format_timestamp(None);
+    if let Some(filters) = &cli.log {
+        builder.parse_filters(filters);
+    }
+    builder.init();
+}
+
+fn run(root: &Path, args: &RunArgs) -> Result<(), Error> {
+    let days = select_days(&args.selection)?;
+    let params: Params = args.params.iter().cloned().collect();
+
+    // Standard input can only be read once, so every day shares it
+    let piped = match &args.input {
+        Some(path) if input::is_stdin(path) => Some(input::read(path)?),
+        Some(_) => None,
+        None if input::piped() => {
+            if days.len() > 1 {
+                return Err(Error::from("Piped input is for a single day, not for --all"));
+            }
+            Some(input::read(Path::new("-"))?)
+        },
+        None => None
+    };
+
+    let jobs = match args.jobs {
+        Some(jobs) => jobs as usize,
+        None => thread::available_parallelism().map_or(1, |jobs| jobs.get())
+    };
+    let start = Instant::now();
+    let mut summary = summary::Summary::new(args.budget);
+    pool::run_ordered(&days, jobs, |day| {
+        let input = match (&args.input, &piped) {
+            (_, Some(input)) => Ok(input.clone()),
+            (Some(path), None) => input::read(path),
+            (None, None) => input::read_cached(root, day)
+        };
+        match input {
+            Ok(input) => solve(day, &input, args, &params),
+            Err(err) => DayRun::failed(err.into())
+        }
+    }, |day, run| {
+        let run = run.unwrap_or_else(|message| DayRun::failed(Error::from(format!("The solution panicked: {}", message))));
+        run.print(day, args.format);
+        summary.add(day, &run);
+    });
+
+    if args.format == output::Format::Pretty && days.len() > 1 {
+        summary.print(start.elapsed(), jobs.min(days.len()));
+    }
+    if summary.failed() > 0 {
+        return Err(Error::from(format!("{} day(s) failed", summary.failed())));
+    }
+    Ok(())
+}
+
+/// What came of solving a day: the answers to the parts that were solved, and the error that
+/// stopped the rest.
+pub struct DayRun {
+    parsed: Option<Duration>,
+    answers: Vec<(Part, Answer, Duration)>,
+    error: Option<Error>,
+    /// Time taken to parse the input and solve both parts
+    elapsed: Duration,
+    /// Most heap memory in use at any time while solving, in bytes
+    peak_memory: usize
+}
+
+impl DayRun {
+    fn failed(err: Error) -> DayRun {
+        DayRun {
+            parsed: None,
+            answers: vec![],
+            error: Some(err),
+            elapsed: Duration::ZERO,
+            peak_memory: 0
+        }
+    }
+
+    fn print(&self, day: &Day, format: output::Format) {
+        format.day(day);
+        if let Some(elapsed) = self.parsed {
+            format.parsed(elapsed);
+        }
+        for (part, answer, elapsed) in &self.answers {
+            format.answer(day, *part, answer, *elapsed);
+        }
+        if let Some(ref err) = self.error {
+            format.failed(day, &Part::ALL[self.answers.len()..], err);
+        }
+    }
+}
+
+/// Solves the parts of a day in order, and exports the day's data and draws its picture if
+/// they were asked for.
+fn solve(day: &Day, input: &str, args: &RunArgs, params: &Params) -> DayRun {
+    let mut parsed = None;
+    let mut answers = vec![];
+    let start = Instant::now();
+    let (result, peak_memory) = memory::measure(|| -> Result<(), Error> {
+        let mut solver = (day.solver)();
+        solver.configure(params)?;
+
+        let start = Instant::now();
+        let model = solver.parse_input(input)?;
+        parsed = Some(start.elapsed());
+
+        // Before the parts, since the data is most useful for inputs a part fails on
+        if let Some(ref path) = args.export {
+            let mut out = BufWriter::new(File::create(path)?);
+            match solver.export(&model, &mut out) {
+                Some(result) => {
+                    result?;
+                    out.flush()?;
+                },
+                None => {
+                    drop(out);
+                    fs::remove_file(path)?;
+                    return Err(format!("{} day {} has no data to export", day.year, day.day).into());
+                }
+            }
+        }
+
+        for &part in &Part::ALL {
+            let start = Instant::now();
+            let answer = solver.solve(part, &model)?;
+            answers.push((part, answer, start.elapsed()));
+        }
+
+        if let Some(ref path) = args.image {
+            let image = solver.image(&model)
+                .ok_or_else(|| format!("{} day {} has no picture to draw", day.year, day.day))??;
+            image.save(path)?;
+        }
+        Ok(())
+    });
+    DayRun {
+        parsed,
+        answers,
+        error: result.err(),
+        elapsed: start.elapsed(),
+        peak_memory
+    }
+}
+
+fn select_days(selection: &Selection) -> Result<Vec<&'static Day>, Error> {
+    let year = selection.year;
+    match selection.day {
+        Some(day) => {
+            let day = registry::find(year, day)
+                .ok_or_else(|| format!("No solution for {} day {}", year, day))?;
+            Ok(vec![day])
+        },
+        None => {
+            let days = registry::year(year);
+            if days.is_empty() {
+                return Err(Error::from(format!("No solutions for {}", year)));
+            }
+            Ok(days)
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use common::{Answer, Error, Part};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::registry::Day;
+use crate::timing::format_duration;
+
+/// How `aoc run` prints its results.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A heading per day with the parse time and both answers, for reading
+    #[default]
+    Pretty,
+    /// One tab separated `year day part answer elapsed` line per part, with the time in
+    /// nanoseconds and the lines of multi-line answers joined by `\n`
+    Plain,
+    /// One JSON object per part and line, with `year`, `day`, `part`, `answer` and `elapsed` in
+    /// nanoseconds, or `error` instead of the answer and time when the part could not be solved
+    Json
+}
+
+/// One line of JSON output. Timings are in nanoseconds.
+#[derive(Serialize)]
+struct Record {
+    year: u32,
+    day: u32,
+    part: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>
+}
+
+impl Format {
+    /// Called before a day is solved.
+    pub fn day(self, day: &Day) {
+        if self == Format::Pretty {
+            println!("{} day {:02}", day.year, day.day);
+        }
+    }
+
+    /// Called once the input of a day is parsed.
+    pub fn parsed(self, elapsed: Duration) {
+        if self == Format::Pretty {
+            println!("  Parsed in {}", format_duration(elapsed));
+        }
+    }
+
+    pub fn answer(self, day: &Day, part: Part, answer: &Answer, elapsed: Duration) {
+        match self {
+            Format::Pretty => {
+                let elapsed = format_duration(elapsed);
+                match answer {
+                    Answer::Grid(lines) => {
+                        println!("  Part {} ({}):", part, elapsed);
+                        for line in lines {
+                            println!("    {}", line);
+                        }
+                    },
+                    _ => println!("  Part {}: {} ({})", part, answer, elapsed)
+                }
+            },
+            Format::Plain => {
+                let answer = answer.to_string().replace('\n', "\\n");
+                println!("{}\t{}\t{}\t{}\t{}", day.year, day.day, part, answer, elapsed.as_nanos());
+            },
+            Format::Json => print_json(&Record {
+                year: day.year,
+                day: day.day,
+                part: part.number(),
+                answer: Some(answer_json(answer)),
+                elapsed: Some(elapsed.as_nanos() as u64),
+                error: None
+            })
+        }
+    }
+
+    /// Called when a day fails, with the parts that were left without an answer.
+    pub fn failed(self, day: &Day, parts: &[Part], err: &Error) {
+        match self {
+            Format::Pretty => println!("  Failed: {}", err),
+            // Keeps standard output to answers only
+            Format::Plain => eprintln!("{} day {:02} failed: {}", day.year, day.day, err),
+            Format::Json => {
+                for &part in parts {
+                    print_json(&Record {
+                        year: day.year,
+                        day: day.day,
+                        part: part.number(),
+                        answer: None,
+                        elapsed: None,
+                        error: Some(err.to_string())
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Numbers stay numbers and multi-line answers become a list of lines, so scripts get the same
+/// shape of answer from a day every time.
+fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(n) => Value::from(*n),
+        Answer::Text(text) => Value::from(text.as_str()),
+        Answer::Grid(lines) => Value::from(lines.clone())
+    }
+}
+
+fn print_json(record: &Record) {
+    // A record of numbers and strings always serializes
+    println!("{}", serde_json::to_string(record).expect("records serialize to JSON"));
+}
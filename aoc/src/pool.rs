@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Stack size of the worker threads, the same as the main thread usually gets, since some
+/// solutions recurse deeply.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Runs `work` on every item with up to `jobs` threads at once. The results are handed to
+/// `done` on the calling thread in the order of the items, each as soon as it and all the ones
+/// before it are ready. Work that panics gives the panic message instead of a result, and the
+/// worker goes on with the next item.
+pub fn run_ordered<T, R, W, D>(items: &[T], jobs: usize, work: W, mut done: D)
+    where T: Sync, R: Send, W: Fn(&T) -> R + Sync, D: FnMut(&T, Result<R, String>)
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= items.len() {
+                        break;
+                    }
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| work(&items[i]))).map_err(panic_message);
+                    if sender.send((i, result)).is_err() {
+                        break;
+                    }
+                })
+                .expect("failed to start a worker thread");
+        }
+        drop(sender);
+
+        let mut ready = BTreeMap::new();
+        let mut expected = 0;
+        for (i, result) in receiver {
+            ready.insert(i, result);
+            while let Some(result) = ready.remove(&expected) {
+                done(&items[expected], result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or("unknown panic", |message| message).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_do_not_hide_later_items() {
+        for jobs in [1, 2] {
+            let mut results = vec![];
+            run_ordered(&[1, 2, 3], jobs, |&n| {
+                if n == 2 {
+                    panic!("no {}", n);
+                }
+                n
+            }, |_, result| results.push(result));
+            assert_eq!(results, vec![Ok(1), Err("no 2".to_string()), Ok(3)]);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use common::{Solution, Solver};
+
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub solver: fn() -> Box<dyn Solver>
+}
+
+fn solver<S: Solution + Default + 'static>() -> Box<dyn Solver> {
+    Box::new(S::default())
+}
+
+macro_rules! day {
+    ($year:expr, $day:expr, $solution:ty) => {
+        Day { year: $year, day: $day, solver: solver::<$solution> }
+    }
+}
+
+const DAYS: &[Day] = &[
+    day!(2018, 1, aoc_2018_01::Day01),
+    day!(2018, 2, aoc_2018_02::Day02),
+    day!(2018, 3, aoc_2018_03::Day03),
+    day!(2018, 4, aoc_2018_04::Day04),
+    day!(2018, 5, aoc_2018_05::Day05),
+    day!(2018, 6, aoc_2018_06::Day06),
+    day!(2018, 7, aoc_2018_07::Day07),
+    day!(2018, 8, aoc_2018_08::Day08),
+    day!(2018, 9, aoc_2018_09::Day09),
+    day!(2018, 10, aoc_2018_10::Day10),
+    day!(2018, 11, aoc_2018_11::Day11),
+    day!(2018, 12, aoc_2018_12::Day12),
+    day!(2018, 13, aoc_2018_13::Day13),
+    day!(2018, 14, aoc_2018_14::Day14),
+    day!(2018, 15, aoc_2018_15::Day15),
+    day!(2018, 16, aoc_2018_16::Day16),
+];
+
+pub fn find(year: u32, day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+pub fn year(year: u32) -> Vec<&'static Day> {
+    DAYS.iter().filter(|d| d.year == year).collect()
+}
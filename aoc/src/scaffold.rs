@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Args;
+use common::Error;
+
+use crate::registry;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs");
+
+#[derive(Args)]
+pub struct NewArgs {
+    #[arg(value_parser = clap::value_parser!(u32).range(2015..))]
+    year: u32,
+
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32
+}
+
+/// Creates the crate for a new day from the templates, and registers it with the runner and the
+/// workspace. An existing day is never overwritten.
+pub fn new(root: &Path, args: &NewArgs) -> Result<(), Error> {
+    let (year, day) = (args.year, args.day);
+    let dir = root.join(year.to_string()).join(format!("{:02}", day));
+    if dir.exists() || registry::find(year, day).is_some() {
+        return Err(Error::from(format!("{} day {} already exists in {}", year, day, dir.display())));
+    }
+
+    let fill = |template: &str| {
+        template
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &format!("{:02}", day))
+    };
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), fill(CARGO_TEMPLATE))?;
+    fs::write(dir.join("src").join("lib.rs"), fill(LIB_TEMPLATE))?;
+
+    register_workspace(root, year)?;
+    register_dependency(root, year, day)?;
+    register_day(root, year, day)?;
+
+    println!("{} day {:02}: created in {}", year, day, dir.display());
+    Ok(())
+}
+
+/// Adds the year's directory to the workspace members, if it is the first day of the year.
+fn register_workspace(root: &Path, year: u32) -> Result<(), Error> {
+    let member = format!("    \"{}/*\",", year);
+    edit_lines(&root.join("Cargo.toml"), |lines| {
+        if lines.contains(&member) {
+            return;
+        }
+        let members = lines.iter().position(|line| line.starts_with("members"));
+        let end = members.and_then(|start| {
+            lines[start..].iter().position(|line| line.trim() == "]").map(|end| start + end)
+        });
+        if let Some(end) = end {
+            lines.insert(end, member.clone());
+        }
+    })
+}
+
+/// Adds the day's crate to the runner's dependencies.
+fn register_dependency(root: &Path, year: u32, day: u32) -> Result<(), Error> {
+    let name = format!("aoc-{}-{:02}", year, day);
+    let line = format!("{} = {{ path = \"../{}/{:02}\" }}", name, year, day);
+    edit_lines(&root.join("aoc").join("Cargo.toml"), |lines| {
+        insert_sorted(lines, line, |line| line.starts_with("aoc-"))
+    })
+}
+
+/// Adds the day to the list of solutions in the registry.
+fn register_day(root: &Path, year: u32, day: u32) -> Result<(), Error> {
+    let line = format!("    day!({}, {}, aoc_{}_{:02}::Day{:02}),", year, day, year, day, day);
+    edit_lines(&root.join("aoc").join("src").join("registry.rs"), |lines| {
+        insert_sorted(lines, line, |line| line.starts_with("    day!("))
+    })
+}
+
+/// Inserts a line among the lines that match, after the last of them that sorts before it. The
+/// lines are compared by the numbers in them, so day 10 comes after day 9.
+fn insert_sorted<F: Fn(&str) -> bool>(lines: &mut Vec<String>, line: String, matches: F) {
+    let key = numbers(&line);
+    let position = lines.iter()
+        .enumerate()
+        .filter(|(_, other)| matches(other))
+        .take_while(|(_, other)| numbers(other) < key)
+        .last()
+        .map(|(i, _)| i + 1)
+        .or_else(|| lines.iter().position(|other| matches(other)))
+        .unwrap_or(lines.len());
+    lines.insert(position, line);
+}
+
+fn numbers(line: &str) -> Vec<u32> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+fn edit_lines<F: FnOnce(&mut Vec<String>)>(path: &Path, edit: F) -> Result<(), Error> {
+    let content = fs::read_to_string(path)?;
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    edit(&mut lines);
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    fn is_day(line: &str) -> bool {
+        line.starts_with("    day!")
+    }
+
+    #[test]
+    fn inserts_by_number_among_matching_lines() {
+        let mut registry = lines("const DAYS: &[Day] = &[\n    day!(2018, 2),\n    day!(2018, 9),\n    day!(2018, 11),\n];");
+        insert_sorted(&mut registry, "    day!(2018, 10),".to_string(), is_day);
+        insert_sorted(&mut registry, "    day!(2018, 1),".to_string(), is_day);
+        insert_sorted(&mut registry, "    day!(2018, 12),".to_string(), is_day);
+        assert_eq!(registry, lines("const DAYS: &[Day] = &[\n    day!(2018, 1),\n    day!(2018, 2),\n    day!(2018, 9),\n    day!(2018, 10),\n    day!(2018, 11),\n    day!(2018, 12),\n];"));
+    }
+
+    #[test]
+    fn inserts_at_the_end_without_matching_lines() {
+        let mut registry = lines("[workspace]");
+        insert_sorted(&mut registry, "    day!(2018, 1),".to_string(), is_day);
+        assert_eq!(registry, lines("[workspace]\n    day!(2018, 1),"));
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use crate::memory::format_bytes;
+use crate::registry::Day;
+use crate::timing::format_duration;
+use crate::DayRun;
+
+/// The time and memory each day took, printed as a table once several days have run.
+pub struct Summary {
+    /// Time a day may take before it is flagged
+    budget: Duration,
+    rows: Vec<Row>
+}
+
+struct Row {
+    year: u32,
+    day: u32,
+    elapsed: Duration,
+    peak_memory: usize,
+    failed: bool
+}
+
+impl Summary {
+    pub fn new(budget: Duration) -> Summary {
+        Summary {
+            budget,
+            rows: vec![]
+        }
+    }
+
+    pub fn add(&mut self, day: &Day, run: &DayRun) {
+        self.rows.push(Row {
+            year: day.year,
+            day: day.day,
+            elapsed: run.elapsed,
+            peak_memory: run.peak_memory,
+            failed: run.error.is_some()
+        });
+    }
+
+    pub fn failed(&self) -> usize {
+        self.rows.iter().filter(|row| row.failed).count()
+    }
+
+    fn over_budget(&self) -> usize {
+        self.rows.iter().filter(|row| row.elapsed > self.budget).count()
+    }
+
+    /// Prints the table, with the wall clock time of the whole run and the number of threads the
+    /// days shared.
+    pub fn print(&self, elapsed: Duration, threads: usize) {
+        println!();
+        println!("Summary, with a budget of {} per day", format_duration(self.budget));
+        println!("  {:<9} {:>9} {:>10}", "Day", "Time", "Memory");
+        for row in &self.rows {
+            let mut flags = vec![];
+            if row.elapsed > self.budget {
+                flags.push("over budget");
+            }
+            if row.failed {
+                flags.push("failed");
+            }
+            let line = format!(
+                "  {} {:02}   {:>9} {:>10}  {}",
+                row.year,
+                row.day,
+                format_duration(row.elapsed),
+                format_bytes(row.peak_memory),
+                flags.join(", ")
+            );
+            println!("{}", line.trim_end());
+        }
+        println!(
+            "{} day(s) in {} on {} thread(s), {} over budget, {} failed",
+            self.rows.len(),
+            format_duration(elapsed),
+            threads,
+            self.over_budget(),
+            self.failed()
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+/// Formats a duration with a unit suited to its size, e.g. `850ns`, `12.3µs`, `4.56ms` or `1.20s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Parses a duration written as a number and a unit, such as `250ms`, `1.5s` or `2m`.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let seconds = match unit {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        "m" => 60.0,
+        _ => return Err(format!("expected a unit of ns, µs, ms, s or m, found '{}'", text))
+    };
+    match number.parse::<f64>() {
+        Ok(number) => Duration::try_from_secs_f64(number * seconds).map_err(|err| err.to_string()),
+        Err(_) => Err(format!("expected a duration like '500ms' or '2s', found '{}'", text))
+    }
+}
+
+/// The spread of a set of timings.
+#[derive(Debug, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration
+}
+
+impl Stats {
+    /// Summarises the timings, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_with_units() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("250µs"), parse_duration("250us"));
+    }
+
+    #[test]
+    fn durations_without_a_number_or_unit() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("500").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("2h").is_err());
+        assert!(parse_duration("-2s").is_err());
+        assert!(parse_duration("1.2.3s").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use clap::Args;
+use common::{Error, Part};
+
+use crate::answers::Answers;
+use crate::registry::Day;
+use crate::{input, select_days, Selection};
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Stores the answers the solutions give for parts without a known answer
+    #[arg(long)]
+    record: bool
+}
+
+enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing(String),
+    Recorded(String),
+    Error(String)
+}
+
+pub fn verify(root: &Path, args: &VerifyArgs) -> Result<(), Error> {
+    let days = select_days(&args.selection)?;
+    let mut answers = Answers::load(root, args.selection.year)?;
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for day in days {
+        println!("{} day {:02}", day.year, day.day);
+        for (part, outcome) in check_day(root, day, &mut answers, args.record) {
+            let (label, detail) = match outcome {
+                Outcome::Pass => ("pass", String::new()),
+                Outcome::Fail { expected, actual } => {
+                    ("fail", format!(": expected {}, got {}", single_line(&expected), single_line(&actual)))
+                },
+                Outcome::Missing(actual) => ("missing", format!(": got {}", single_line(&actual))),
+                Outcome::Recorded(actual) => ("recorded", format!(": {}", single_line(&actual))),
+                Outcome::Error(err) => ("error", format!(": {}", err))
+            };
+            println!("  Part {}: {}{}", part, label, detail);
+            *counts.entry(label).or_default() += 1;
+        }
+    }
+
+    if args.record {
+        answers.save()?;
+    }
+
+    let summary = counts.iter().map(|(label, n)| format!("{} {}", n, label)).collect::<Vec<_>>();
+    println!("{}", summary.join(", "));
+
+    let failed = counts.get("fail").unwrap_or(&0) + counts.get("error").unwrap_or(&0);
+    if failed > 0 {
+        return Err(Error::from(format!("{} part(s) did not match the known answers", failed)));
+    }
+    Ok(())
+}
+
+fn check_day(root: &Path, day: &Day, answers: &mut Answers, record: bool) -> Vec<(Part, Outcome)> {
+    let solver = (day.solver)();
+    let model = input::read_cached(root, day)
+        .map_err(Error::from)
+        .and_then(|input| solver.parse_input(&input));
+    let model = match model {
+        Ok(model) => model,
+        Err(err) => return Part::ALL.iter().map(|&part| (part, Outcome::Error(err.to_string()))).collect()
+    };
+
+    Part::ALL.iter().map(|&part| {
+        let actual = match solver.solve(part, &model) {
+            Ok(answer) => answer.to_string(),
+            Err(err) => return (part, Outcome::Error(err.to_string()))
+        };
+        let outcome = match answers.get(day.day, part) {
+            Some(expected) if expected == actual => Outcome::Pass,
+            // A wrong answer never replaces a known one
+            Some(expected) => Outcome::Fail { expected: expected.to_string(), actual },
+            None if record => {
+                answers.set(day.day, part, actual.clone());
+                Outcome::Recorded(actual)
+            },
+            None => Outcome::Missing(actual)
+        };
+        (part, outcome)
+    }).collect()
+}
+
+/// Keeps multi-line answers on one line in the report.
+fn single_line(answer: &str) -> String {
+    answer.replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use crate::registry;
+
+    #[test]
+    fn record_keeps_known_answers() {
+        let root = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+        let day = registry::find(2018, 1).unwrap();
+        let input = input::path(&root, 2018, 1);
+        fs::create_dir_all(input.parent().unwrap()).unwrap();
+        fs::write(&input, "+1\n-1\n").unwrap();
+        fs::write(root.join("2018").join("answers.json"), r#"{"1": {"1": "5"}}"#).unwrap();
+
+        let mut answers = Answers::load(&root, 2018).unwrap();
+        let outcomes = check_day(&root, day, &mut answers, true);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(matches!(&outcomes[0], (Part::One, Outcome::Fail { expected, actual }) if expected == "5" && actual == "0"));
+        assert!(matches!(&outcomes[1], (Part::Two, Outcome::Recorded(actual)) if actual == "0"));
+        assert_eq!(answers.get(1, Part::One), Some("5"));
+        assert_eq!(answers.get(1, Part::Two), Some("0"));
+    }
+}
@@ -0,0 +1,41 @@
+use common::{Answer, Error, Solution};
+
+#[derive(Default)]
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Model = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(&self, _model: &Vec<String>) -> Result<Answer, Error> {
+        Err(Error::unsolvable("part 1 is not solved yet"))
+    }
+
+    fn part2(&self, _model: &Vec<String>) -> Result<Answer, Error> {
+        Err(Error::unsolvable("part 2 is not solved yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::{assert_part1, assert_part2};
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "needs the example from the puzzle text"]
+    fn part1_example() {
+        assert_part1(&Day{day}, EXAMPLE, 0);
+    }
+
+    #[test]
+    #[ignore = "needs the example from the puzzle text"]
+    fn part2_example() {
+        assert_part2(&Day{day}, EXAMPLE, 0);
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The puzzle input did not have the expected format
+    Parse(ParseError),
+    Io(io::Error),
+    /// The input was understood, but the puzzle has no answer for it
+    Unsolvable(String),
+    Other(String)
+}
+
+/// Where in the input a parse error happened, and what was expected there. Lines and columns
+/// count from 1, and are filled in by whichever caller knows them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: String,
+    pub expected: String
+}
+
+impl Error {
+    pub fn parse(text: &str, expected: &str) -> Error {
+        Error::Parse(ParseError {
+            line: None,
+            column: None,
+            text: text.to_string(),
+            expected: expected.to_string()
+        })
+    }
+
+    pub fn unsolvable(reason: &str) -> Error {
+        Error::Unsolvable(reason.to_string())
+    }
+
+    /// Records the line a parse error happened on, unless it is already known.
+    pub fn at_line(mut self, line: usize) -> Error {
+        if let Error::Parse(ref mut err) = self {
+            err.line = err.line.or(Some(line));
+        }
+        self
+    }
+
+    /// Records the column a parse error happened at, unless it is already known.
+    pub fn at_column(mut self, column: usize) -> Error {
+        if let Error::Parse(ref mut err) = self {
+            err.column = err.column.or(Some(column));
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Unsolvable(reason) => write!(f, "No solution: {}", reason),
+            Error::Other(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: ", line, column)?,
+            (Some(line), None) => write!(f, "line {}: ", line)?,
+            (None, Some(column)) => write!(f, "column {}: ", column)?,
+            (None, None) => {}
+        }
+        write!(f, "expected {}, found '{}'", self.expected, self.text)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Error {
+        Error::Other(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Error {
+        Error::Other(message.to_string())
+    }
+}
+
+/// Parses every line of the input, recording the line number in any error.
+pub fn parse_lines<T: FromStr<Err = Error>>(input: &str) -> Result<Vec<T>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|err: Error| err.at_line(i + 1)))
+        .collect()
+}
@@ -0,0 +1,250 @@
+use std::ops::{Index, IndexMut};
+
+use crate::Error;
+
+/// Offsets of the four orthogonal neighbours, in reading order.
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of all eight neighbours, in reading order.
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1)
+];
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Cells are addressed by `(x, y)`, with `(0, 0)` in the top left corner. `get` and `get_mut`
+/// check the bounds, while indexing with `grid[(x, y)]` panics outside of the grid like indexing a
+/// slice does.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height]
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with each cell set to the value `f` gives for its position.
+    pub fn from_fn<F: FnMut((usize, usize)) -> T>(width: usize, height: usize, f: F) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells
+        }
+    }
+
+    /// Parses a character map, one row per line, with `f` turning each character into a cell.
+    /// All rows must be equally wide, and errors from `f` get the line and column of the
+    /// character.
+    pub fn parse<F>(input: &str, mut f: F) -> Result<Grid<T>, Error>
+        where F: FnMut((usize, usize), char) -> Result<T, Error>
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (y, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = f((x, y), c).map_err(|err| err.at_line(y + 1).at_column(x + 1))?;
+                cells.push(cell);
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    let expected = format!("a row {} characters wide", width);
+                    return Err(Error::parse(line, &expected).at_line(y + 1));
+                },
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[self.offset(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.offset(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// The cells of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` does not allow a chunk size of 0, which an empty grid would ask for
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The positions of all cells, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// All cells, in reading order.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The positions of the four orthogonal neighbours inside the grid, in reading order.
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(position, &NEIGHBOURS_4)
+    }
+
+    /// The positions of all eight neighbours inside the grid, in reading order.
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(position, &NEIGHBOURS_8)
+    }
+
+    /// Renders the grid back to text, with `f` turning each cell into a character. Every row ends
+    /// with a newline.
+    pub fn render<F: FnMut((usize, usize), &T) -> char>(&self, mut f: F) -> String {
+        let mut buf = String::with_capacity((self.width + 1) * self.height);
+        for (position, cell) in self.iter() {
+            buf.push(f(position, cell));
+            if position.0 + 1 == self.width {
+                buf.push('\n');
+            }
+        }
+        buf
+    }
+
+    fn neighbours(&self, (x, y): (usize, usize), offsets: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            if self.contains(position) {
+                Some(position)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        x + y * self.width
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        assert!(self.contains(position), "{:?} is outside a {}x{} grid", position, self.width, self.height);
+        &self.cells[self.offset(position)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        assert!(self.contains(position), "{:?} is outside a {}x{} grid", position, self.width, self.height);
+        let index = self.offset(position);
+        &mut self.cells[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+
+    fn digits(input: &str) -> Result<Grid<u32>, Error> {
+        Grid::parse(input, |_, c| c.to_digit(10).ok_or_else(|| Error::parse(&c.to_string(), "a digit")))
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.render(|_, &n| char::from_digit(n, 10).unwrap()), "123\n456\n");
+    }
+
+    #[test]
+    fn ragged_rows_are_an_error() {
+        let err = digits("123\n45\n789").err().unwrap();
+        assert!(matches!(err, Error::Parse(ParseError { line: Some(2), column: None, .. })), "{:?}", err);
+    }
+
+    #[test]
+    fn cell_errors_have_a_line_and_column() {
+        let err = digits("123\n4x6").err().unwrap();
+        assert!(matches!(err, Error::Parse(ParseError { line: Some(2), column: Some(2), .. })), "{:?}", err);
+    }
+
+    #[test]
+    fn positions_outside_the_grid() {
+        let mut grid = Grid::new(3, 2, 0);
+        assert!(grid.contains((2, 1)));
+        assert!(!grid.contains((3, 0)));
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.get_mut((3, 1)), None);
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours8((2, 1)).collect::<Vec<_>>(), vec![(1, 0), (2, 0), (1, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 3x2 grid")]
+    fn indexing_outside_the_grid_panics() {
+        let grid = Grid::new(3, 2, 0);
+        let _ = grid[(3, 0)];
+    }
+}
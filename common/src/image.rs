@@ -0,0 +1,165 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::{Error, Grid};
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// A picture of a puzzle, such as a heatmap of a grid, that can be saved as a PNG or PPM file.
+///
+/// The encoders are written out here rather than pulled in, since pictures of puzzles are small
+/// enough that PNG files without compression do fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>
+}
+
+impl Image {
+    /// Paints every cell of a grid as a `scale` by `scale` square, with the colour `f` picks for
+    /// it.
+    pub fn from_grid<T, F>(grid: &Grid<T>, scale: usize, mut f: F) -> Image
+        where F: FnMut((usize, usize), &T) -> Rgb
+    {
+        let scale = scale.max(1);
+        let colours = Grid::from_fn(grid.width(), grid.height(), |position| f(position, &grid[position]));
+        let (width, height) = (grid.width() * scale, grid.height() * scale);
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / scale, y / scale)))
+            .map(|cell| colours[cell])
+            .collect();
+        Image { width, height, pixels }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, (x, y): (usize, usize)) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Saves the image as a PPM file if the path ends in `.ppm`, and as a PNG file otherwise.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|ext| ext == "ppm") {
+            self.write_ppm(&mut writer)?;
+        } else {
+            self.write_png(&mut writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes a binary PPM (P6) file.
+    pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())
+    }
+
+    /// Writes an 8 bit RGB PNG file, with the image data stored in uncompressed deflate blocks.
+    pub fn write_png<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = vec![];
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // Bit depth 8, colour type 2 (RGB), default compression, filtering and no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        write_chunk(out, b"IHDR", &header)?;
+
+        // Every row starts with its filter type, and none is used
+        let mut rows = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            rows.push(0);
+            rows.extend(row.iter().flatten());
+        }
+        write_chunk(out, b"IDAT", &zlib_stored(&rows))?;
+        write_chunk(out, b"IEND", &[])
+    }
+}
+
+/// A colour from dark purple through red and orange to pale yellow, for `t` from 0 to 1.
+pub fn heat(t: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [[0, 0, 4], [87, 16, 110], [188, 55, 84], [249, 142, 9], [252, 255, 164]];
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) } * (STOPS.len() - 1) as f64;
+    let i = (t as usize).min(STOPS.len() - 2);
+    let fraction = t - i as f64;
+    let mut colour = [0; 3];
+    for (c, (&from, &to)) in colour.iter_mut().zip(STOPS[i].iter().zip(STOPS[i + 1].iter())) {
+        *c = (f64::from(from) + (f64::from(to) - f64::from(from)) * fraction).round() as u8;
+    }
+    colour
+}
+
+/// A colour for the `n`th of a set of things that should look different from each other. Hues
+/// are spread by the golden angle, so neighbouring numbers get far apart colours.
+pub fn palette(n: usize) -> Rgb {
+    let hue = (n as f64 * 137.507_764) % 360.0;
+    let (saturation, value) = (0.65, 0.95);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match (hue / 60.0) as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x)
+    };
+    let m = value - chroma;
+    [r, g, b].map(|c| ((c + m) * 255.0).round() as u8)
+}
+
+fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data.iter()));
+    out.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps data in a zlib stream of stored deflate blocks, which hold at most 65535 bytes each.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        stream.push(if blocks.peek().is_none() { 1 } else { 0 });
+        let length = block.len() as u16;
+        stream.extend_from_slice(&length.to_le_bytes());
+        stream.extend_from_slice(&(!length).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32<'a, I: Iterator<Item = &'a u8>>(bytes: I) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
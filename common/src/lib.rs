@@ -0,0 +1,22 @@
+mod answer;
+mod error;
+mod grid;
+pub mod image;
+mod params;
+mod parser;
+mod point;
+mod random;
+mod recorder;
+mod solution;
+pub mod testing;
+
+pub use crate::answer::Answer;
+pub use crate::error::{parse_lines, Error, ParseError};
+pub use crate::grid::Grid;
+pub use crate::image::Image;
+pub use crate::params::Params;
+pub use crate::parser::{char_grid, Parser};
+pub use crate::point::{Coordinate, Lexicographic, Order, Point, Point2, Point3, Point4, ReadingOrder};
+pub use crate::random::Rng;
+pub use crate::recorder::Recorder;
+pub use crate::solution::{Model, Part, Solution, Solver};
@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Named values given to a solution from the command line, such as `workers=2`, for the puzzle
+/// values that are not part of the input file.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: BTreeMap<String, String>
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    /// Fails on any parameter that is not one of the `known` ones, so a misspelled parameter is
+    /// not silently ignored.
+    pub fn expect_only(&self, known: &[&str]) -> Result<(), Error> {
+        match self.values.keys().find(|key| !known.contains(&key.as_str())) {
+            None => Ok(()),
+            Some(key) if known.is_empty() => Err(Error::from(format!("Unknown parameter '{}', this solution takes no parameters", key))),
+            Some(key) => Err(Error::from(format!("Unknown parameter '{}', expected one of: {}", key, known.join(", "))))
+        }
+    }
+
+    /// Parses the value of a parameter, if it was given.
+    pub fn get<T: FromStr>(&self, key: &str, expected: &str) -> Result<Option<T>, Error> {
+        match self.values.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| Error::parse(&format!("{}={}", key, value), expected))
+        }
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Params {
+        let mut params = Params::new();
+        for (key, value) in iter {
+            params.insert(key.as_ref(), value.as_ref());
+        }
+        params
+    }
+}
@@ -0,0 +1,226 @@
+use std::str::FromStr;
+
+use crate::{Error, Grid};
+
+/// Reads a line of input from left to right, one piece at a time.
+///
+/// Every piece is checked where it stands, so an error points at the column where the line
+/// stopped matching, says what belonged there, and shows the rest of the line from that point.
+/// `format` describes the whole line, like `a claim like '#123 @ 3,2: 5x4'`, and is part of
+/// every error.
+pub struct Parser<'a> {
+    line: &'a str,
+    position: usize,
+    format: &'a str
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(line: &'a str, format: &'a str) -> Parser<'a> {
+        Parser {
+            line,
+            position: 0,
+            format
+        }
+    }
+
+    /// The part of the line that has not been read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// An error at the current position, saying `what` was expected there.
+    pub fn error(&self, what: &str) -> Error {
+        let expected = format!("{} in {}", what, self.format);
+        Error::parse(self.rest(), &expected).at_column(self.column())
+    }
+
+    /// The column of the current position, counting characters from 1.
+    pub fn column(&self) -> usize {
+        self.line[..self.position].chars().count() + 1
+    }
+
+    /// Reads exactly `token`.
+    pub fn token(&mut self, token: &str) -> Result<(), Error> {
+        if !self.rest().starts_with(token) {
+            return Err(self.error(&format!("'{}'", token)));
+        }
+        self.position += token.len();
+        Ok(())
+    }
+
+    /// Skips any whitespace.
+    pub fn spaces(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Reads characters for as long as `f` accepts them, possibly none.
+    pub fn take_while<F: FnMut(char) -> bool>(&mut self, mut f: F) -> &'a str {
+        let rest = self.rest();
+        let length = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    /// Reads an integer, with an optional `+` or `-` sign. Numbers that do not fit in `T`, and
+    /// negative numbers for unsigned types, are errors at the start of the number.
+    pub fn int<T: FromStr>(&mut self) -> Result<T, Error> {
+        let start = self.position;
+        if self.rest().starts_with(['+', '-']) {
+            self.position += 1;
+        }
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let number = &self.line[start..self.position];
+        match number.parse() {
+            Ok(number) if !digits.is_empty() => Ok(number),
+            _ => {
+                self.position = start;
+                Err(self.error("a number"))
+            }
+        }
+    }
+
+    /// Reads any single character.
+    pub fn char(&mut self) -> Result<char, Error> {
+        self.char_with(Ok)
+    }
+
+    /// Reads a single character and turns it into a value with `f`. Errors from `f` get the
+    /// column of the character.
+    pub fn char_with<T, F>(&mut self, f: F) -> Result<T, Error>
+        where F: FnOnce(char) -> Result<T, Error>
+    {
+        let c = self.rest().chars().next().ok_or_else(|| self.error("a character"))?;
+        let value = f(c).map_err(|err| err.at_column(self.column()))?;
+        self.position += c.len_utf8();
+        Ok(value)
+    }
+
+    /// Reads one or more items with `item`, with `separator` between each of them.
+    pub fn list<T, F>(&mut self, separator: &str, mut item: F) -> Result<Vec<T>, Error>
+        where F: FnMut(&mut Parser<'a>) -> Result<T, Error>
+    {
+        let mut items = vec![item(self)?];
+        while self.rest().starts_with(separator) {
+            self.position += separator.len();
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Reads exactly `N` integers with `separator` between each of them, and any whitespace
+    /// around them. A separator of only whitespace, like `" "`, takes any amount of whitespace.
+    pub fn ints<T: FromStr + Copy, const N: usize>(&mut self, separator: &str) -> Result<[T; N], Error> {
+        self.spaces();
+        let first = self.int()?;
+        let mut values = [first; N];
+        for value in values.iter_mut().skip(1) {
+            self.spaces();
+            self.token(separator.trim())?;
+            self.spaces();
+            *value = self.int()?;
+        }
+        self.spaces();
+        Ok(values)
+    }
+
+    /// Reads a vector of `N` integers in brackets, like `[3, 2, 1, 1]`.
+    pub fn vector<T: FromStr + Copy, const N: usize>(&mut self) -> Result<[T; N], Error> {
+        self.token("[")?;
+        let values = self.ints(",")?;
+        self.token("]")?;
+        Ok(values)
+    }
+
+    /// Checks that the whole line has been read, apart from trailing whitespace.
+    pub fn end(&mut self) -> Result<(), Error> {
+        self.spaces();
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the line"))
+        }
+    }
+}
+
+/// Parses a character map with `Grid::parse`, turning every character into the cell it is paired
+/// with in `cells`. Any other character is an error at its line and column.
+pub fn char_grid<T: Clone>(input: &str, cells: &[(char, T)], expected: &str) -> Result<Grid<T>, Error> {
+    Grid::parse(input, |_, c| {
+        cells
+            .iter()
+            .find(|(cell, _)| *cell == c)
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| Error::parse(&c.to_string(), expected))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+
+    fn column(err: Error) -> Option<usize> {
+        match err {
+            Error::Parse(ParseError { column, .. }) => column,
+            _ => None
+        }
+    }
+
+    #[test]
+    fn reads_pieces_in_order() {
+        let mut parser = Parser::new("#12 @ 3,-2: 5x4", "a claim");
+        parser.token("#").unwrap();
+        assert_eq!(parser.int::<u32>().unwrap(), 12);
+        parser.spaces();
+        parser.token("@").unwrap();
+        parser.spaces();
+        assert_eq!(parser.ints::<i32, 2>(",").unwrap(), [3, -2]);
+        parser.token(":").unwrap();
+        parser.spaces();
+        assert_eq!(parser.list("x", |parser| parser.int::<u32>()).unwrap(), vec![5, 4]);
+        assert!(parser.end().is_ok());
+    }
+
+    #[test]
+    fn numbers_that_do_not_fit() {
+        let mut parser = Parser::new("x 300", "a byte");
+        parser.token("x ").unwrap();
+        let err = parser.int::<u8>().err().unwrap();
+        assert_eq!(column(err), Some(3));
+        // The parser stays at the start of the number
+        assert_eq!(parser.rest(), "300");
+        assert_eq!(column(Parser::new("-1", "a count").int::<u32>().err().unwrap()), Some(1));
+        assert_eq!(column(Parser::new("4294967296", "a count").int::<u32>().err().unwrap()), Some(1));
+    }
+
+    #[test]
+    fn wrong_number_of_ints() {
+        let mut parser = Parser::new("[1, 2, 3]", "registers");
+        assert_eq!(column(parser.vector::<u32, 4>().err().unwrap()), Some(9));
+        let mut parser = Parser::new("[1, 2, 3, 4, 5]", "registers");
+        assert_eq!(column(parser.vector::<u32, 4>().err().unwrap()), Some(12));
+        let mut parser = Parser::new("1 2", "an instruction");
+        assert_eq!(column(parser.ints::<u32, 3>(" ").err().unwrap()), Some(4));
+        let mut parser = Parser::new("1 2 3 4", "an instruction");
+        parser.ints::<u32, 3>(" ").unwrap();
+        assert_eq!(column(parser.end().err().unwrap()), Some(7));
+    }
+
+    #[test]
+    fn errors_say_what_was_expected() {
+        let err = Parser::new("abc", "a claim like '#1 @ 1,1: 1x1'").token("#").err().unwrap();
+        assert!(err.to_string().contains("'#' in a claim like '#1 @ 1,1: 1x1'"), "{}", err);
+    }
+
+    #[test]
+    fn grids_of_characters() {
+        let grid = char_grid("#.\n.#", &[('#', true), ('.', false)], "a wall or floor").unwrap();
+        assert_eq!(grid.values().filter(|&&wall| wall).count(), 2);
+        let err = char_grid("#.\n.x", &[('#', true), ('.', false)], "a wall or floor").err().unwrap();
+        assert!(matches!(err, Error::Parse(ParseError { line: Some(2), column: Some(2), .. })));
+    }
+}
@@ -0,0 +1,428 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::{Error, Parser};
+
+/// A number that can be used as the coordinate of a point.
+pub trait Coordinate: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn abs_diff(self, other: $t) -> $t {
+                    if self > other { self - other } else { other - self }
+                }
+
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: $t) -> Option<$t> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    }
+}
+
+coordinate!(i32, i64, isize, u32, u64, usize);
+
+/// How points are sorted.
+pub trait Order {
+    fn cmp<T: Ord>(a: &[T], b: &[T]) -> Ordering;
+}
+
+/// Sorts points by their first coordinate, then by the second and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Lexicographic;
+
+impl Order for Lexicographic {
+    fn cmp<T: Ord>(a: &[T], b: &[T]) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Sorts points by their last coordinate first, so 2D points are sorted top to bottom and then
+/// left to right, the way text is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ReadingOrder;
+
+impl Order for ReadingOrder {
+    fn cmp<T: Ord>(a: &[T], b: &[T]) -> Ordering {
+        a.iter().rev().cmp(b.iter().rev())
+    }
+}
+
+/// A point, or a vector between points, in `N` dimensions. `O` decides how points are sorted.
+///
+/// Points display and parse as their coordinates separated by commas, like `3,4`.
+pub struct Point<T, const N: usize, O = Lexicographic> {
+    coordinates: [T; N],
+    order: PhantomData<O>
+}
+
+pub type Point2<T, O = Lexicographic> = Point<T, 2, O>;
+pub type Point3<T, O = Lexicographic> = Point<T, 3, O>;
+pub type Point4<T, O = Lexicographic> = Point<T, 4, O>;
+
+impl<T: Coordinate, const N: usize, O> Point<T, N, O> {
+    pub fn from_coordinates(coordinates: [T; N]) -> Point<T, N, O> {
+        Point {
+            coordinates,
+            order: PhantomData
+        }
+    }
+
+    pub fn origin() -> Point<T, N, O> {
+        Point::from_coordinates([T::ZERO; N])
+    }
+
+    pub fn coordinates(&self) -> [T; N] {
+        self.coordinates
+    }
+
+    /// The same point, sorted in another order.
+    pub fn with_order<P>(self) -> Point<T, N, P> {
+        Point::from_coordinates(self.coordinates)
+    }
+
+    /// The sum of the distances along each axis, the number of orthogonal steps between points.
+    pub fn manhattan(&self, other: &Point<T, N, O>) -> T {
+        self.zip_map(other, T::abs_diff)
+            .coordinates
+            .iter()
+            .fold(T::ZERO, |sum, &d| sum + d)
+    }
+
+    /// The largest distance along any axis, the number of steps between points when diagonal
+    /// steps are allowed.
+    pub fn chebyshev(&self, other: &Point<T, N, O>) -> T {
+        self.zip_map(other, T::abs_diff)
+            .coordinates
+            .iter()
+            .fold(T::ZERO, |max, &d| max.max(d))
+    }
+
+    /// The straight line distance between points.
+    pub fn euclidean(&self, other: &Point<T, N, O>) -> f64 {
+        self.zip_map(other, T::abs_diff)
+            .coordinates
+            .iter()
+            .map(|d| d.to_f64() * d.to_f64())
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// The smallest coordinates of both points along each axis.
+    pub fn component_min(&self, other: &Point<T, N, O>) -> Point<T, N, O> {
+        self.zip_map(other, T::min)
+    }
+
+    /// The largest coordinates of both points along each axis.
+    pub fn component_max(&self, other: &Point<T, N, O>) -> Point<T, N, O> {
+        self.zip_map(other, T::max)
+    }
+
+    /// The points one step away along a single axis. Points that would fall outside the range of
+    /// the coordinate type are left out.
+    pub fn orthogonal_neighbours(&self) -> Vec<Point<T, N, O>> where O: Order {
+        let mut neighbours = vec![];
+        for axis in 0..N {
+            for &step in &[T::checked_sub, T::checked_add] {
+                if let Some(c) = step(self.coordinates[axis], T::ONE) {
+                    let mut coordinates = self.coordinates;
+                    coordinates[axis] = c;
+                    neighbours.push(Point::from_coordinates(coordinates));
+                }
+            }
+        }
+        neighbours.sort();
+        neighbours
+    }
+
+    /// All points one step away, diagonals included. Points that would fall outside the range of
+    /// the coordinate type are left out.
+    pub fn neighbours(&self) -> Vec<Point<T, N, O>> where O: Order {
+        let mut neighbours: Vec<[T; N]> = vec![self.coordinates];
+        for axis in 0..N {
+            neighbours = neighbours
+                .into_iter()
+                .flat_map(|coordinates| {
+                    let c = coordinates[axis];
+                    vec![c.checked_sub(T::ONE), Some(c), c.checked_add(T::ONE)]
+                        .into_iter()
+                        .flatten()
+                        .map(move |c| {
+                            let mut coordinates = coordinates;
+                            coordinates[axis] = c;
+                            coordinates
+                        })
+                })
+                .collect();
+        }
+        let mut neighbours: Vec<Point<T, N, O>> = neighbours
+            .into_iter()
+            .filter(|&coordinates| coordinates != self.coordinates)
+            .map(Point::from_coordinates)
+            .collect();
+        neighbours.sort();
+        neighbours
+    }
+
+    fn zip_map<F: Fn(T, T) -> T>(&self, other: &Point<T, N, O>, f: F) -> Point<T, N, O> {
+        let mut coordinates = self.coordinates;
+        for (c, &o) in coordinates.iter_mut().zip(other.coordinates.iter()) {
+            *c = f(*c, o);
+        }
+        Point::from_coordinates(coordinates)
+    }
+}
+
+impl<T: Coordinate, O> Point<T, 2, O> {
+    pub fn new(x: T, y: T) -> Point<T, 2, O> {
+        Point::from_coordinates([x, y])
+    }
+}
+
+impl<T: Coordinate, O> Point<T, 3, O> {
+    pub fn new(x: T, y: T, z: T) -> Point<T, 3, O> {
+        Point::from_coordinates([x, y, z])
+    }
+
+    pub fn z(&self) -> T {
+        self.coordinates[2]
+    }
+}
+
+impl<T: Coordinate, O> Point<T, 4, O> {
+    pub fn new(x: T, y: T, z: T, w: T) -> Point<T, 4, O> {
+        Point::from_coordinates([x, y, z, w])
+    }
+
+    pub fn z(&self) -> T {
+        self.coordinates[2]
+    }
+
+    pub fn w(&self) -> T {
+        self.coordinates[3]
+    }
+}
+
+impl<T: Coordinate, const N: usize, O> Point<T, N, O> {
+    pub fn x(&self) -> T {
+        self.coordinates[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.coordinates[1]
+    }
+}
+
+impl<T: Coordinate, O> From<(T, T)> for Point<T, 2, O> {
+    fn from((x, y): (T, T)) -> Point<T, 2, O> {
+        Point::from_coordinates([x, y])
+    }
+}
+
+impl<T: Coordinate, O> From<(T, T, T)> for Point<T, 3, O> {
+    fn from((x, y, z): (T, T, T)) -> Point<T, 3, O> {
+        Point::from_coordinates([x, y, z])
+    }
+}
+
+impl<T: Coordinate, O> From<(T, T, T, T)> for Point<T, 4, O> {
+    fn from((x, y, z, w): (T, T, T, T)) -> Point<T, 4, O> {
+        Point::from_coordinates([x, y, z, w])
+    }
+}
+
+impl<T: Coordinate, const N: usize, O> Add for Point<T, N, O> {
+    type Output = Point<T, N, O>;
+
+    fn add(self, other: Point<T, N, O>) -> Point<T, N, O> {
+        self.zip_map(&other, T::add)
+    }
+}
+
+impl<T: Coordinate, const N: usize, O> Sub for Point<T, N, O> {
+    type Output = Point<T, N, O>;
+
+    fn sub(self, other: Point<T, N, O>) -> Point<T, N, O> {
+        self.zip_map(&other, T::sub)
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>, const N: usize, O> Neg for Point<T, N, O> {
+    type Output = Point<T, N, O>;
+
+    fn neg(self) -> Point<T, N, O> {
+        Point::origin() - self
+    }
+}
+
+/// Scales every coordinate.
+impl<T: Coordinate, const N: usize, O> Mul<T> for Point<T, N, O> {
+    type Output = Point<T, N, O>;
+
+    fn mul(self, factor: T) -> Point<T, N, O> {
+        self.zip_map(&Point::from_coordinates([factor; N]), T::mul)
+    }
+}
+
+impl<T: Coordinate, const N: usize, O> AddAssign for Point<T, N, O> {
+    fn add_assign(&mut self, other: Point<T, N, O>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Coordinate, const N: usize, O> SubAssign for Point<T, N, O> {
+    fn sub_assign(&mut self, other: Point<T, N, O>) {
+        *self = *self - other;
+    }
+}
+
+// The traits below are implemented by hand, since deriving them would require the order marker
+// to implement them as well.
+
+impl<T: Copy, const N: usize, O> Clone for Point<T, N, O> {
+    fn clone(&self) -> Point<T, N, O> {
+        *self
+    }
+}
+
+impl<T: Copy, const N: usize, O> Copy for Point<T, N, O> {}
+
+impl<T: PartialEq, const N: usize, O> PartialEq for Point<T, N, O> {
+    fn eq(&self, other: &Point<T, N, O>) -> bool {
+        self.coordinates == other.coordinates
+    }
+}
+
+impl<T: Eq, const N: usize, O> Eq for Point<T, N, O> {}
+
+impl<T: Hash, const N: usize, O> Hash for Point<T, N, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.coordinates.hash(state);
+    }
+}
+
+impl<T: Ord, const N: usize, O: Order> PartialOrd for Point<T, N, O> {
+    fn partial_cmp(&self, other: &Point<T, N, O>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord, const N: usize, O: Order> Ord for Point<T, N, O> {
+    fn cmp(&self, other: &Point<T, N, O>) -> Ordering {
+        O::cmp(&self.coordinates, &other.coordinates)
+    }
+}
+
+impl<T: fmt::Display, const N: usize, O> fmt::Display for Point<T, N, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, c) in self.coordinates.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, O> fmt::Debug for Point<T, N, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Point{:?}", self.coordinates)
+    }
+}
+
+/// Parses coordinates separated by commas, with any whitespace around them, like `181, 184`.
+impl<T: Coordinate + FromStr, const N: usize, O> FromStr for Point<T, N, O> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Point<T, N, O>, Error> {
+        let format = format!("{} coordinates separated by commas", N);
+        let mut parser = Parser::new(s, &format);
+        let coordinates = parser.ints(",")?;
+        parser.end()?;
+        Ok(Point::from_coordinates(coordinates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours_in_three_dimensions() {
+        let neighbours = Point3::<i32>::new(0, 0, 0).neighbours();
+        assert_eq!(neighbours.len(), 26);
+        assert_eq!(neighbours[0], Point3::new(-1, -1, -1));
+        assert_eq!(neighbours[25], Point3::new(1, 1, 1));
+        assert!(!neighbours.contains(&Point3::origin()));
+        assert_eq!(Point3::<i32>::new(0, 0, 0).orthogonal_neighbours().len(), 6);
+    }
+
+    #[test]
+    fn neighbours_in_four_dimensions() {
+        let point = Point4::<i32>::new(1, 2, 3, 4);
+        let neighbours = point.neighbours();
+        assert_eq!(neighbours.len(), 80);
+        assert!(neighbours.iter().all(|neighbour| neighbour.chebyshev(&point) == 1));
+        assert_eq!(point.orthogonal_neighbours().len(), 8);
+    }
+
+    #[test]
+    fn neighbours_stay_within_the_coordinate_type() {
+        assert_eq!(Point3::<u32>::new(0, 0, 0).neighbours().len(), 7);
+        assert_eq!(Point2::<u32>::new(0, 5).orthogonal_neighbours(), vec![Point2::new(0, 4), Point2::new(0, 6), Point2::new(1, 5)]);
+    }
+
+    #[test]
+    fn reading_order() {
+        let mut points: Vec<Point2<i32, ReadingOrder>> = vec![Point2::new(2, 1), Point2::new(5, 0), Point2::new(1, 1), Point2::new(0, 2)];
+        points.sort();
+        assert_eq!(points, vec![Point2::new(5, 0), Point2::new(1, 1), Point2::new(2, 1), Point2::new(0, 2)]);
+        assert!(Point2::<i32>::new(5, 0) > Point2::new(1, 1));
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point2::<i32>::new(1, 2), Point2::new(4, -2));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.euclidean(&b), 5.0);
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let point: Point3<i64> = " 1, -2 ,3".parse().unwrap();
+        assert_eq!(point, Point3::new(1, -2, 3));
+        assert_eq!(point.to_string(), "1,-2,3");
+        assert!("1,2".parse::<Point3<i64>>().is_err());
+        assert!("1,2,3,4".parse::<Point3<i64>>().is_err());
+    }
+}
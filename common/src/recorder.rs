@@ -0,0 +1,51 @@
+/// Collects the rendered states of a simulation, one frame per tick, so they can be played back
+/// afterwards.
+///
+/// Rendering is left to a closure, which is only called while the recorder has room for more
+/// frames. Simulations can then take a recorder unconditionally, and solving with
+/// `Recorder::off()` costs next to nothing.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    frames: Vec<String>,
+    limit: usize
+}
+
+impl Recorder {
+    /// A recorder that keeps the first `limit` frames and ignores the rest.
+    pub fn new(limit: usize) -> Recorder {
+        Recorder {
+            frames: vec![],
+            limit
+        }
+    }
+
+    /// A recorder that keeps no frames, for running a simulation without watching it.
+    pub fn off() -> Recorder {
+        Recorder::new(0)
+    }
+
+    /// True once no more frames will be kept, so simulations can skip work that is only needed
+    /// for rendering.
+    pub fn is_full(&self) -> bool {
+        self.frames.len() >= self.limit
+    }
+
+    pub fn record<F: FnOnce() -> String>(&mut self, render: F) {
+        if !self.is_full() {
+            self.frames.push(render());
+        }
+    }
+
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// The widest line and the most lines of any frame, which is the screen size needed to show
+    /// them all.
+    pub fn size(&self) -> (usize, usize) {
+        self.frames.iter().fold((0, 0), |(width, height), frame| {
+            let frame_width = frame.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+            (width.max(frame_width), height.max(frame.lines().count()))
+        })
+    }
+}
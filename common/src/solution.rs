@@ -0,0 +1,156 @@
+use std::any::Any;
+use std::fmt;
+use std::io::Write;
+
+use crate::{Answer, Error, Image, Params, Recorder, Rng};
+
+/// A solution to one day's puzzle.
+///
+/// Parsing is kept apart from solving, so the parsed model can be shared by both parts and each
+/// stage can be run and timed on its own.
+pub trait Solution {
+    /// The puzzle input, parsed into the form both parts are solved from
+    type Model;
+
+    /// Applies the parameters given on the command line. Solutions with puzzle values that are
+    /// not part of the input take them here, and keep their defaults for any that are missing.
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        params.expect_only(&[])
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Error>;
+
+    /// Whether the puzzle values are built into the solution, so the day can be solved without
+    /// an input file. Such solutions parse an empty input into those values.
+    fn builtin_input(&self) -> bool {
+        false
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Answer, Error>;
+
+    fn part2(&self, model: &Self::Model) -> Result<Answer, Error>;
+
+    /// Generates a random input in the puzzle's format, for stress and property testing. `size`
+    /// is roughly the number of lines or items in the input, and the same seed and size always
+    /// give the same input. Solutions without a generator return `None`.
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Runs the simulation behind a part, recording a frame for each tick, so it can be watched.
+    /// Solutions that do not simulate anything return `None`.
+    fn animate(&self, _part: Part, _model: &Self::Model, _recorder: &mut Recorder) -> Option<Result<(), Error>> {
+        None
+    }
+
+    /// Draws a picture of the puzzle, such as a heatmap of a grid. Solutions without one return
+    /// `None`.
+    fn image(&self, _model: &Self::Model) -> Option<Result<Image, Error>> {
+        None
+    }
+
+    /// Writes out the data behind the answers as a CSV table to `out`, row by row, for studying a
+    /// puzzle beyond its answers. Solutions without one return `None`.
+    fn export(&self, _model: &Self::Model, _out: &mut dyn Write) -> Option<Result<(), Error>> {
+        None
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A parsed model whose type is only known to the solution that created it.
+pub struct Model(Box<dyn Any>);
+
+impl Model {
+    fn downcast<S: Solution>(&self) -> Result<&S::Model, Error> where S::Model: 'static {
+        Ok(self.0.downcast_ref::<S::Model>().ok_or("Model was parsed by a different solution")?)
+    }
+}
+
+/// The object safe side of `Solution`, which lets tools hold the solutions for every day in one
+/// list. It is implemented for all solutions.
+pub trait Solver {
+    fn configure(&mut self, params: &Params) -> Result<(), Error>;
+
+    fn parse_input(&self, input: &str) -> Result<Model, Error>;
+
+    fn builtin_input(&self) -> bool;
+
+    fn solve(&self, part: Part, model: &Model) -> Result<Answer, Error>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+
+    fn animate(&self, part: Part, model: &Model, recorder: &mut Recorder) -> Option<Result<(), Error>>;
+
+    fn image(&self, model: &Model) -> Option<Result<Image, Error>>;
+
+    fn export(&self, model: &Model, out: &mut dyn Write) -> Option<Result<(), Error>>;
+}
+
+impl<S> Solver for S where S: Solution, S::Model: 'static {
+    fn configure(&mut self, params: &Params) -> Result<(), Error> {
+        Solution::configure(self, params)
+    }
+
+    fn parse_input(&self, input: &str) -> Result<Model, Error> {
+        Ok(Model(Box::new(self.parse(input)?)))
+    }
+
+    fn builtin_input(&self) -> bool {
+        Solution::builtin_input(self)
+    }
+
+    fn solve(&self, part: Part, model: &Model) -> Result<Answer, Error> {
+        let model = model.downcast::<S>()?;
+        match part {
+            Part::One => self.part1(model),
+            Part::Two => self.part2(model)
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Solution::generate(self, rng, size)
+    }
+
+    fn animate(&self, part: Part, model: &Model, recorder: &mut Recorder) -> Option<Result<(), Error>> {
+        match model.downcast::<S>() {
+            Ok(model) => Solution::animate(self, part, model, recorder),
+            Err(err) => Some(Err(err))
+        }
+    }
+
+    fn image(&self, model: &Model) -> Option<Result<Image, Error>> {
+        match model.downcast::<S>() {
+            Ok(model) => Solution::image(self, model),
+            Err(err) => Some(Err(err))
+        }
+    }
+
+    fn export(&self, model: &Model, out: &mut dyn Write) -> Option<Result<(), Error>> {
+        match model.downcast::<S>() {
+            Ok(model) => Solution::export(self, model, out),
+            Err(err) => Some(Err(err))
+        }
+    }
+}
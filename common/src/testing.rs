@@ -0,0 +1,225 @@
+//! Helpers for testing solutions against the examples in the puzzle texts, and against slow
+//! reference solutions on generated inputs.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{Answer, Part, Rng, Solution};
+
+/// Solves both parts of an example and compares the answers, so a test is one line:
+///
+/// ```ignore
+/// assert_example(&Day01::default(), "+1\n-2\n+3\n+1", 3, 2);
+/// ```
+#[track_caller]
+pub fn assert_example<S, A, B>(solution: &S, input: &str, part1: A, part2: B)
+    where S: Solution, A: Into<Answer>, B: Into<Answer>
+{
+    assert_part(solution, Part::One, input, part1);
+    assert_part(solution, Part::Two, input, part2);
+}
+
+/// Solves part 1 of an example, for puzzles whose examples only cover one part.
+#[track_caller]
+pub fn assert_part1<S: Solution, A: Into<Answer>>(solution: &S, input: &str, expected: A) {
+    assert_part(solution, Part::One, input, expected);
+}
+
+/// Solves part 2 of an example, for puzzles whose examples only cover one part.
+#[track_caller]
+pub fn assert_part2<S: Solution, A: Into<Answer>>(solution: &S, input: &str, expected: A) {
+    assert_part(solution, Part::Two, input, expected);
+}
+
+#[track_caller]
+pub fn assert_part<S: Solution, A: Into<Answer>>(solution: &S, part: Part, input: &str, expected: A) {
+    let model = match solution.parse(input) {
+        Ok(model) => model,
+        Err(err) => panic!("Could not parse the example: {}", err)
+    };
+    let answer = match part {
+        Part::One => solution.part1(&model),
+        Part::Two => solution.part2(&model)
+    };
+    match answer {
+        Ok(answer) => assert_eq!(answer, expected.into(), "Wrong answer to part {} of the example", part),
+        Err(err) => panic!("Could not solve part {} of the example: {}", part, err)
+    }
+}
+
+/// Checks a solution's input generator over a spread of seeds and sizes: the same seed must give
+/// the same input, and every input must parse.
+#[track_caller]
+pub fn assert_generates<S: Solution>(solution: &S) {
+    for seed in 0..20 {
+        for &size in &[1, 2, 5, 20, 100] {
+            let input = match solution.generate(&mut Rng::new(seed), size) {
+                Some(input) => input,
+                None => panic!("The solution has no input generator")
+            };
+            let again = solution.generate(&mut Rng::new(seed), size);
+            assert_eq!(Some(&input), again.as_ref(), "Seed {} gave two different inputs of size {}", seed, size);
+            if let Err(err) = solution.parse(&input) {
+                panic!("Could not parse the input generated from seed {} with size {}: {}\n{}", seed, size, err, input);
+            }
+        }
+    }
+}
+
+/// Solves both parts of inputs generated over a spread of seeds at the given sizes, for days whose
+/// generators promise more than inputs that parse.
+#[track_caller]
+pub fn assert_solves_generated<S: Solution>(solution: &S, sizes: &[usize]) {
+    for seed in 0..20 {
+        for &size in sizes {
+            let input = match solution.generate(&mut Rng::new(seed), size) {
+                Some(input) => input,
+                None => panic!("The solution has no input generator")
+            };
+            let model = match solution.parse(&input) {
+                Ok(model) => model,
+                Err(err) => panic!("Could not parse the input generated from seed {} with size {}: {}\n{}", seed, size, err, input)
+            };
+            for &part in &Part::ALL {
+                let answer = match part {
+                    Part::One => solution.part1(&model),
+                    Part::Two => solution.part2(&model)
+                };
+                if let Err(err) = answer {
+                    panic!("Could not solve part {} of the input generated from seed {} with size {}: {}\n{}", part, seed, size, err, input);
+                }
+            }
+        }
+    }
+}
+
+/// Solves generated inputs with both a solution and a slow reference solution that is clearly
+/// correct, and panics on the first input where they disagree. The input is shrunk before it is
+/// reported, by removing lines and characters for as long as the two still disagree.
+///
+/// The reference returns `None` for parts it does not cover, and for inputs outside what the
+/// puzzle promises, which shrinking can produce. Those are then not solved at all.
+/// Panics in either of them count as an answer, so a solution that panics where the reference
+/// does not is reported too.
+#[track_caller]
+pub fn assert_matches_reference<S, F>(solution: &S, seeds: u64, sizes: &[usize], reference: F)
+    where S: Solution, F: Fn(Part, &S::Model) -> Option<Answer>
+{
+    for seed in 0..seeds {
+        for &size in sizes {
+            let input = match solution.generate(&mut Rng::new(seed), size) {
+                Some(input) => input,
+                None => panic!("The solution has no input generator")
+            };
+            let mut mismatch = match compare(solution, &reference, &input) {
+                Some(mismatch) => mismatch,
+                None => continue
+            };
+
+            // The last input that still disagreed is the one shrinking ends on
+            let input = shrink(&input, |input| match compare(solution, &reference, input) {
+                Some(found) => {
+                    mismatch = found;
+                    true
+                },
+                None => false
+            });
+            panic!(
+                "Part {} disagrees with the reference on an input generated from seed {} with size {}\n\
+                 reference: {}\nsolution:  {}\nshrunk input:\n{}",
+                mismatch.part, seed, size, mismatch.expected, mismatch.actual, input
+            );
+        }
+    }
+}
+
+struct Mismatch {
+    part: Part,
+    expected: String,
+    actual: String
+}
+
+/// Solves an input with both the solution and the reference, returning the first part where they
+/// disagree. Inputs the solution cannot parse have nothing to disagree on.
+fn compare<S, F>(solution: &S, reference: &F, input: &str) -> Option<Mismatch>
+    where S: Solution, F: Fn(Part, &S::Model) -> Option<Answer>
+{
+    let model = solution.parse(input).ok()?;
+    Part::ALL.iter().find_map(|&part| {
+        let expected = match catch(|| reference(part, &model)) {
+            Ok(Some(answer)) => answer.to_string(),
+            Ok(None) => return None,
+            Err(message) => message
+        };
+        let actual = match catch(|| match part {
+            Part::One => solution.part1(&model),
+            Part::Two => solution.part2(&model)
+        }) {
+            Ok(Ok(answer)) => answer.to_string(),
+            Ok(Err(err)) => format!("error: {}", err),
+            Err(message) => message
+        };
+        if expected == actual {
+            None
+        } else {
+            Some(Mismatch { part, expected, actual })
+        }
+    })
+}
+
+/// Runs `f`, turning a panic into its message.
+fn catch<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        format!("panic: {}", message)
+    })
+}
+
+/// Shrinks an input for as long as `fails` holds, first by removing whole lines and then by
+/// removing characters from each line, until neither removes anything more.
+fn shrink<F: FnMut(&str) -> bool>(input: &str, mut fails: F) -> String {
+    let mut lines: Vec<String> = input.lines().map(str::to_string).collect();
+    loop {
+        let before = lines.clone();
+        lines = remove_chunks(lines, |lines| fails(&join_lines(lines)));
+        for i in 0..lines.len() {
+            let chars: Vec<char> = lines[i].chars().collect();
+            let chars = remove_chunks(chars, |chars| {
+                let mut candidate = lines.clone();
+                candidate[i] = chars.iter().collect();
+                fails(&join_lines(&candidate))
+            });
+            lines[i] = chars.into_iter().collect();
+        }
+        if lines == before {
+            return join_lines(&lines);
+        }
+    }
+}
+
+fn join_lines(lines: &[String]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Removes chunks of items for as long as `fails` holds, halving the chunk size down to single
+/// items.
+fn remove_chunks<T: Clone, F: FnMut(&[T]) -> bool>(mut items: Vec<T>, mut fails: F) -> Vec<T> {
+    let mut chunk = (items.len() / 2).max(1);
+    loop {
+        let mut start = 0;
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate: Vec<T> = items[..start].iter().chain(&items[end..]).cloned().collect();
+            if !candidate.is_empty() && fails(&candidate) {
+                items = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        if chunk == 1 {
+            return items;
+        }
+        chunk /= 2;
+    }
+}
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use common::Solution;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc_2018_03::Day03.parse(input);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use common::Solution;
+
+// Fights the battle too, so caves where it never ends show up as timeouts
+fuzz_target!(|input: &str| {
+    if let Ok(system) = input.parse::<aoc_2018_15::System>() {
+        let _ = aoc_2018_15::Day15.part1(&system);
+    }
+});